@@ -0,0 +1,149 @@
+#!/usr/bin/env python3
+"""Generates src/unicode/tables.rs, the grapheme break and display width tables, from the Unicode
+database that comes with Python.
+
+    python3 scripts/unicode_tables.py > src/unicode/tables.rs
+
+unicodedata doesn't have the grapheme break property itself, so it's derived from the general
+categories the way UAX #29 defines it, with the few properties Python doesn't know about
+(Other_Grapheme_Extend, Prepend, Extended_Pictographic, ...) listed here by hand.
+"""
+
+import sys
+import unicodedata
+
+OTHER_GRAPHEME_EXTEND = [
+    (0x09BE, 0x09BE), (0x09D7, 0x09D7), (0x0B3E, 0x0B3E), (0x0B57, 0x0B57), (0x0BBE, 0x0BBE),
+    (0x0BD7, 0x0BD7), (0x0CC2, 0x0CC2), (0x0CD5, 0x0CD6), (0x0D3E, 0x0D3E), (0x0D57, 0x0D57),
+    (0x0DCF, 0x0DCF), (0x0DDF, 0x0DDF), (0x1B35, 0x1B35), (0x200C, 0x200C), (0x302E, 0x302F),
+    (0xFF9E, 0xFF9F), (0x1133E, 0x1133E), (0x11357, 0x11357), (0x114B0, 0x114B0),
+    (0x114BD, 0x114BD), (0x115AF, 0x115AF), (0x11930, 0x11930), (0x1D165, 0x1D165),
+    (0x1D16E, 0x1D172), (0xE0020, 0xE007F),
+]
+
+EMOJI_MODIFIER = [(0x1F3FB, 0x1F3FF)]
+
+PREPEND = [
+    (0x0600, 0x0605), (0x06DD, 0x06DD), (0x070F, 0x070F), (0x0890, 0x0891), (0x08E2, 0x08E2),
+    (0x0D4E, 0x0D4E), (0x110BD, 0x110BD), (0x110CD, 0x110CD), (0x111C2, 0x111C3),
+    (0x1193F, 0x1193F), (0x11941, 0x11941), (0x11A3A, 0x11A3A), (0x11A84, 0x11A89),
+    (0x11D46, 0x11D46),
+]
+
+# spacing marks that don't count as SpacingMark, and letters that do
+NOT_SPACING_MARK = [
+    (0x102B, 0x102C), (0x1038, 0x1038), (0x1062, 0x1064), (0x1067, 0x106D), (0x1083, 0x1083),
+    (0x1087, 0x108C), (0x108F, 0x108F), (0x109A, 0x109C), (0x1A61, 0x1A61), (0x1A63, 0x1A64),
+    (0xAA7B, 0xAA7B), (0xAA7D, 0xAA7D), (0x11720, 0x11721),
+]
+EXTRA_SPACING_MARK = [(0x0E33, 0x0E33), (0x0EB3, 0x0EB3)]
+
+EXTENDED_PICTOGRAPHIC = [
+    (0x00A9, 0x00A9), (0x00AE, 0x00AE), (0x203C, 0x203C), (0x2049, 0x2049), (0x2122, 0x2122),
+    (0x2139, 0x2139), (0x2194, 0x2199), (0x21A9, 0x21AA), (0x231A, 0x231B), (0x2328, 0x2328),
+    (0x2388, 0x2388), (0x23CF, 0x23CF), (0x23E9, 0x23F3), (0x23F8, 0x23FA), (0x24C2, 0x24C2),
+    (0x25AA, 0x25AB), (0x25B6, 0x25B6), (0x25C0, 0x25C0), (0x25FB, 0x25FE), (0x2600, 0x2605),
+    (0x2607, 0x2612), (0x2614, 0x2685), (0x2690, 0x2705), (0x2708, 0x2712), (0x2714, 0x2714),
+    (0x2716, 0x2716), (0x271D, 0x271D), (0x2721, 0x2721), (0x2728, 0x2728), (0x2733, 0x2734),
+    (0x2744, 0x2744), (0x2747, 0x2747), (0x274C, 0x274C), (0x274E, 0x274E), (0x2753, 0x2755),
+    (0x2757, 0x2757), (0x2763, 0x2767), (0x2795, 0x2797), (0x27A1, 0x27A1), (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF), (0x2934, 0x2935), (0x2B05, 0x2B07), (0x2B1B, 0x2B1C), (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55), (0x3030, 0x3030), (0x303D, 0x303D), (0x3297, 0x3297), (0x3299, 0x3299),
+    (0x1F000, 0x1F0FF), (0x1F10D, 0x1F10F), (0x1F12F, 0x1F12F), (0x1F16C, 0x1F171),
+    (0x1F17E, 0x1F17F), (0x1F18E, 0x1F18E), (0x1F191, 0x1F19A), (0x1F1AD, 0x1F1E5),
+    (0x1F201, 0x1F20F), (0x1F21A, 0x1F21A), (0x1F22F, 0x1F22F), (0x1F232, 0x1F23A),
+    (0x1F23C, 0x1F23F), (0x1F249, 0x1F3FA), (0x1F400, 0x1F53D), (0x1F546, 0x1F64F),
+    (0x1F680, 0x1F6FF), (0x1F774, 0x1F77F), (0x1F7D5, 0x1F7FF), (0x1F80C, 0x1F80F),
+    (0x1F848, 0x1F84F), (0x1F85A, 0x1F85F), (0x1F888, 0x1F88F), (0x1F8AE, 0x1F8FF),
+    (0x1F90C, 0x1F93A), (0x1F93C, 0x1F945), (0x1F947, 0x1FAFF), (0x1FC00, 0x1FFFD),
+]
+
+
+def within(ranges, cp):
+    return any(start <= cp <= end for start, end in ranges)
+
+
+def grapheme_break(cp):
+    c = chr(cp)
+    category = unicodedata.category(c)
+    if cp == 0x0D:
+        return "Cr"
+    if cp == 0x0A:
+        return "Lf"
+    if cp == 0x200D:
+        return "Zwj"
+    if within(PREPEND, cp):
+        return "Prepend"
+    if category in ("Mn", "Me") or within(OTHER_GRAPHEME_EXTEND, cp) or within(EMOJI_MODIFIER, cp):
+        return "Extend"
+    if category in ("Cc", "Cf", "Zl", "Zp"):
+        return "Control"
+    if 0x1F1E6 <= cp <= 0x1F1FF:
+        return "RegionalIndicator"
+    if (category == "Mc" and not within(NOT_SPACING_MARK, cp)) or within(EXTRA_SPACING_MARK, cp):
+        return "SpacingMark"
+    if 0x1100 <= cp <= 0x115F or 0xA960 <= cp <= 0xA97C:
+        return "L"
+    if 0x1160 <= cp <= 0x11A7 or 0xD7B0 <= cp <= 0xD7C6:
+        return "V"
+    if 0x11A8 <= cp <= 0x11FF or 0xD7CB <= cp <= 0xD7FB:
+        return "T"
+    if 0xAC00 <= cp <= 0xD7A3:
+        # LV and LVT syllables alternate, which is quicker to work out than to look up
+        return None
+    if within(EXTENDED_PICTOGRAPHIC, cp):
+        return "Pictographic"
+    return None
+
+
+def display_width(cp):
+    """How many terminal cells a code point takes up on its own: 0 for combining marks and other
+    invisible ones, 2 for East Asian wide and fullwidth ones, and 1 for everything else (which
+    isn't listed)."""
+    c = chr(cp)
+    category = unicodedata.category(c)
+    # Hangul vowels and final consonants that combine with the initial one before them
+    if 0x1160 <= cp <= 0x11FF or 0xD7B0 <= cp <= 0xD7FF or cp == 0x200B:
+        return 0
+    # the soft hyphen is shown as a hyphen by most terminals
+    if category in ("Mn", "Me") or (category == "Cf" and cp != 0x00AD):
+        return 0
+    if unicodedata.east_asian_width(c) in ("W", "F"):
+        return 2
+    return None
+
+
+def runs(prop):
+    """Merges the code points that have the same value of `prop` into ranges."""
+    ranges = []
+    for cp in range(0x110000):
+        if 0xD800 <= cp <= 0xDFFF:
+            continue
+        value = prop(cp)
+        if value is None:
+            continue
+        if ranges and ranges[-1][1] == cp - 1 and ranges[-1][2] == value:
+            ranges[-1][1] = cp
+        else:
+            ranges.append([cp, cp, value])
+    return ranges
+
+
+def main():
+    out = sys.stdout
+    out.write("// Generated by scripts/unicode_tables.py from Unicode %s. Don't edit by hand.\n\n"
+              % unicodedata.unidata_version)
+    out.write("use super::Gcb;\n\n")
+    out.write("/// Grapheme cluster break property of every code point that isn't `Other`, by range.\n")
+    out.write("pub const GRAPHEME_BREAK: &[(u32, u32, Gcb)] = &[\n")
+    for start, end, value in runs(grapheme_break):
+        out.write("    (0x%x, 0x%x, Gcb::%s),\n" % (start, end, value))
+    out.write("];\n\n")
+    out.write("/// Display width of every code point that isn't 1 cell wide, by range.\n")
+    out.write("pub const WIDTH: &[(u32, u32, u8)] = &[\n")
+    for start, end, value in runs(display_width):
+        out.write("    (0x%x, 0x%x, %d),\n" % (start, end, value))
+    out.write("];\n")
+
+
+main()
@@ -0,0 +1,383 @@
+//! Screen columns in long lines. Where something ends up on screen depends on everything before
+//! it on its line, since a tab goes to the next tab stop from wherever it is. So the cursor's
+//! column, or what's at the left edge of a screen scrolled sideways, would take laying out the
+//! line up to there every time the screen is drawn. Long lines instead get the columns of places
+//! along the way remembered, and only the text from the closest one needs laying out.
+//!
+//! Wrapping a line onto rows is the same, only more so, since the rows it's wrapped onto have to be
+//! counted from the start of the line. So the rows of long lines are remembered too, and after an
+//! edit, wrapping picks up again a row before it and goes until the rows are back to where they
+//! were before, which is usually a row or two later.
+
+use std::collections::HashMap;
+use std::iter;
+use std::mem;
+use std::ops::Range;
+use std::rc::Rc;
+
+use crate::layout::{self, Row, Wrapper};
+use crate::rope::Rope;
+use crate::unicode;
+
+/// Roughly how many bytes apart the places with known columns are. Lines no longer than this are
+/// just laid out from the start.
+const SPACING: usize = 4096;
+
+/// Where every line starts.
+const START: &[(usize, usize)] = &[(0, 0)];
+
+#[derive(Debug, Default)]
+pub struct Columns {
+    /// The tab width the columns were worked out with.
+    tab_width: usize,
+    /// The tab width and screen width the rows were wrapped with.
+    wrapped_for: (usize, usize),
+    /// Byte offsets in long lines and the columns they're at, in order, by line, as far into each
+    /// line as it's been laid out. They're on boundaries between clusters that don't depend on
+    /// what comes before, so laying out from one of them comes out the same as from the start of
+    /// the line.
+    lines: HashMap<usize, Vec<(usize, usize)>>,
+    /// The rows long lines are wrapped onto, by line.
+    wrapped: HashMap<usize, Wrapped>,
+}
+
+/// The rows a long line is wrapped onto.
+#[derive(Debug)]
+struct Wrapped {
+    /// The rows from the start of the line, as far as they're known to be right.
+    rows: Rc<Vec<Row>>,
+    /// The start and column of the row after `rows`, to pick up wrapping from, unless `rows`
+    /// already go to the end of the line.
+    next: Option<(usize, usize)>,
+    /// Rows from before the last edits, from after them, moved along with their text but with
+    /// the columns they had. Once wrapping gets to the start of one of these again, at a column
+    /// that's a whole number of tab stops from it, the rest of the rows come out the same too.
+    stale: Vec<Row>,
+}
+
+impl Columns {
+    /// Forgets everything, for when lines were added or taken away and so have new numbers.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.wrapped.clear();
+    }
+
+    /// Forgets what an edit of `line` changed, which replaced byte range `range` of it with
+    /// `inserted` bytes: the columns from there on, and the rows from the one before it on.
+    pub fn edited(&mut self, line: usize, range: Range<usize>, inserted: usize) {
+        if let Some(known) = self.lines.get_mut(&line) {
+            known.truncate(known.partition_point(|&(o, _)| o < range.start).max(1));
+        }
+
+        let Some(wrapped) = self.wrapped.get_mut(&line) else {
+            return;
+        };
+        // where a row starts only depends on where the one before it does, but where that one
+        // ends can depend on what's at the start of the next, so wrapping picks up again at the
+        // start of the row before the edited one
+        let rows = Rc::make_mut(&mut wrapped.rows);
+        let (next, stale) = match wrapped.next {
+            Some((start, _)) if start <= range.start => (rows.pop(), mem::take(&mut wrapped.stale)),
+            // the rows from before an edit that wasn't wrapped again yet stop there, and the
+            // stale ones after it are the ones that go on to the end
+            Some(_) => {
+                let keep = rows
+                    .partition_point(|row| row.start <= range.start)
+                    .saturating_sub(2);
+                let next = rows.drain(keep..).next();
+                (next, mem::take(&mut wrapped.stale))
+            }
+            None => {
+                let keep = rows
+                    .partition_point(|row| row.start <= range.start)
+                    .saturating_sub(2);
+                let stale = rows.split_off(keep);
+                (stale.first().copied(), stale)
+            }
+        };
+        wrapped.next = Some(next.map_or((0, 0), |row| (row.start, row.col)));
+        wrapped.stale = stale
+            .into_iter()
+            .filter(|row| row.start > range.end)
+            .map(|row| Row {
+                start: row.start - range.len() + inserted,
+                end: row.end - range.len() + inserted,
+                col: row.col,
+            })
+            .collect();
+    }
+
+    /// The screen column that byte `offset` of `line` is at. `line` is the line's byte range in
+    /// `text`, without its line ending.
+    pub fn col_of_offset(
+        &mut self,
+        text: &Rope,
+        line: Range<usize>,
+        offset: usize,
+        tab_width: usize,
+    ) -> usize {
+        let known = self.known(text, line.clone(), tab_width, |(o, _)| o >= offset);
+        let (from, col) = known[known.partition_point(|&(o, _)| o <= offset) - 1];
+
+        let section = text.slice(line.start + from..line.start + offset);
+        let mut glyphs = layout::glyphs_at(&section, tab_width, col);
+        glyphs.skip_to(usize::MAX);
+        glyphs.position().1
+    }
+
+    /// The part of `line` that's laid out on columns `cols`, give or take: the byte range of it
+    /// in the line, which starts on a glyph at or before `cols.start` and goes on past the glyph
+    /// at `cols.end` (unless the line ends first), and the column it starts at.
+    pub fn section(
+        &mut self,
+        text: &Rope,
+        line: Range<usize>,
+        cols: Range<usize>,
+        tab_width: usize,
+    ) -> (Range<usize>, usize) {
+        let len = line.len();
+        let known = self.known(text, line, tab_width, |(_, col)| col > cols.end);
+        let (start, col) = known[known.partition_point(|&(_, col)| col <= cols.start) - 1];
+        let end = known
+            .get(known.partition_point(|&(_, col)| col <= cols.end))
+            .map_or(len, |&(o, _)| o);
+
+        (start..end, col)
+    }
+
+    /// The rows `line` is wrapped onto, on a screen `screen_width` columns wide.
+    pub fn rows(
+        &mut self,
+        text: &Rope,
+        line: Range<usize>,
+        tab_width: usize,
+        screen_width: usize,
+    ) -> Rc<Vec<Row>> {
+        if line.len() <= SPACING {
+            return Rc::new(layout::wrap(&text.slice(line), tab_width, screen_width));
+        }
+        if (tab_width, screen_width) != self.wrapped_for {
+            self.wrapped.clear();
+            self.wrapped_for = (tab_width, screen_width);
+        }
+
+        let wrapped = self
+            .wrapped
+            .entry(text.byte_to_line(line.start))
+            .or_insert_with(|| Wrapped {
+                rows: Rc::default(),
+                next: Some((0, 0)),
+                stale: Vec::new(),
+            });
+        if let Some(next) = wrapped.next.take() {
+            wrap_from(wrapped, next, text, line, tab_width, screen_width);
+        }
+
+        Rc::clone(&wrapped.rows)
+    }
+
+    /// The places in `line` with known columns, worked out as far as the first one that's `far`
+    /// enough, or the end of the line.
+    fn known(
+        &mut self,
+        text: &Rope,
+        line: Range<usize>,
+        tab_width: usize,
+        far: impl Fn((usize, usize)) -> bool,
+    ) -> &[(usize, usize)] {
+        if line.len() <= SPACING {
+            return START;
+        }
+        if tab_width != self.tab_width {
+            self.lines.clear();
+            self.tab_width = tab_width;
+        }
+
+        let known = self
+            .lines
+            .entry(text.byte_to_line(line.start))
+            .or_insert_with(|| START.to_vec());
+        while let Some(&(offset, col)) = known.last() {
+            if offset == line.len() || far((offset, col)) {
+                break;
+            }
+
+            let end = boundary_after(text, line.start + offset + SPACING, line.end);
+            let section = text.slice(line.start + offset..end);
+            let mut glyphs = layout::glyphs_at(&section, tab_width, col);
+            glyphs.skip_to(usize::MAX);
+            known.push((end - line.start, glyphs.position().1));
+        }
+
+        known
+    }
+}
+
+/// Wraps the rest of `line`, from a row that starts at byte `start` of it and at column `col`,
+/// until the rows come out the same as the stale ones again, or the line ends.
+fn wrap_from(
+    wrapped: &mut Wrapped,
+    (start, col): (usize, usize),
+    text: &Rope,
+    line: Range<usize>,
+    tab_width: usize,
+    screen_width: usize,
+) {
+    let mut stale = mem::take(&mut wrapped.stale).into_iter().peekable();
+    let rows = Rc::make_mut(&mut wrapped.rows);
+    let mut wrapper = Wrapper::new(screen_width, start, col);
+    let (mut offset, mut col) = (start, col);
+    while offset < line.len() {
+        let end = boundary_after(text, line.start + offset + SPACING, line.end) - line.start;
+        let section = text.slice(line.start + offset..line.start + end);
+        let mut glyphs = layout::glyphs_at(&section, tab_width, col);
+        for glyph in glyphs.by_ref() {
+            let count = rows.len();
+            wrapper.push(offset, &glyph, rows);
+            if rows.len() == count {
+                continue;
+            }
+
+            let row = wrapper.row();
+            while stale.next_if(|old| old.start < row.start).is_some() {}
+            let same = |old: &Row| {
+                old.start == row.start && old.col.abs_diff(row.col).is_multiple_of(tab_width)
+            };
+            if let Some(first) = stale.next_if(same) {
+                // tabs line up the same from here on, only shifted over
+                let moved = iter::once(first).chain(stale).map(|old| Row {
+                    col: old.col - first.col + row.col,
+                    ..old
+                });
+                rows.extend(moved);
+                return;
+            }
+        }
+        (offset, col) = (end, glyphs.position().1);
+    }
+
+    wrapper.finish(line.len(), rows);
+}
+
+/// The first place at or after byte `offset` of `text` that's a cluster boundary no matter what
+/// comes before it, or `end` if there's none before that.
+fn boundary_after(text: &Rope, offset: usize, end: usize) -> usize {
+    let mut offset = text.floor_char_boundary(offset.min(end));
+    while offset < end {
+        let (Some(prev), Some(next)) = (text.char_before(offset), text.char_at(offset)) else {
+            break;
+        };
+        if unicode::is_boundary(prev, next) {
+            break;
+        }
+        offset += next.len_utf8();
+    }
+
+    offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Random;
+
+    /// Checks glyphs all along `line` in `text` against laying out the whole line.
+    fn check(columns: &mut Columns, text: &Rope, line: Range<usize>, tab_width: usize) {
+        let whole = text.slice(line.clone());
+        for glyph in layout::glyphs(&whole, tab_width).step_by(61) {
+            let col = columns.col_of_offset(text, line.clone(), glyph.offset, tab_width);
+            assert_eq!(col, glyph.col);
+
+            let (section, start) =
+                columns.section(text, line.clone(), glyph.col..glyph.col, tab_width);
+            assert!(section.start <= glyph.offset && glyph.offset < section.end);
+            assert_eq!(
+                columns.col_of_offset(text, line.clone(), section.start, tab_width),
+                start
+            );
+            assert!(section.len() <= 2 * SPACING + 8);
+        }
+    }
+
+    #[test]
+    fn long_lines() {
+        let line = "\tab日e\u{301}\u{1f1fa}\u{1f1f8}\x01".repeat(2000);
+        let mut text = Rope::new(&format!("short\n{line}\nshort"));
+        let mut columns = Columns::default();
+        let range = 6..6 + line.len();
+        check(&mut columns, &text, range.clone(), 4);
+        assert!(columns.lines[&1].len() > line.len() / SPACING);
+
+        // an edit in the middle changes the columns after it, and not before
+        let offset = range.start + line.len() / 2 + 1;
+        let offset = text.floor_char_boundary(offset);
+        columns.edited(1, offset - range.start..offset - range.start, 1);
+        text.insert(offset, "\t");
+        check(&mut columns, &text, range.start..range.end + 1, 4);
+
+        check(&mut columns, &text, range.start..range.end + 1, 8);
+        assert_eq!(columns.col_of_offset(&text, 0..5, 5, 4), 5);
+    }
+
+    #[test]
+    fn no_boundaries() {
+        // one cluster that goes on and on, which can't be split anywhere
+        let line = format!("e{}", "\u{301}".repeat(3 * SPACING));
+        let text = Rope::new(&line);
+        let mut columns = Columns::default();
+        assert_eq!(
+            columns.col_of_offset(&text, 0..line.len(), line.len(), 4),
+            1
+        );
+        assert_eq!(
+            columns.section(&text, 0..line.len(), 0..1, 4),
+            (0..line.len(), 0)
+        );
+    }
+
+    #[test]
+    fn rows_after_edits() {
+        let pieces = [
+            "word ",
+            "a",
+            " ",
+            "\t",
+            "日本",
+            "e\u{301}",
+            "\u{1f1fa}",
+            "longerword",
+        ];
+        let mut random = Random(0x9e37_79b9_7f4a_7c15);
+        let mut line = String::new();
+        while line.len() < 2 * SPACING {
+            line.push_str(random.pick(&pieces));
+        }
+        let mut text = Rope::new(&format!("short\n{line}"));
+        let mut columns = Columns::default();
+
+        for round in 0..150 {
+            let width = [7, 40, 80][round / 50];
+            // a few edits at a time, so some of them are of rows that aren't wrapped again yet
+            for _ in 0..random.below(3) + 1 {
+                let offset = random.offset(&line);
+                if random.below(2) == 0 {
+                    let piece = random.pick(&pieces);
+                    line.insert_str(offset, piece);
+                    text.insert(6 + offset, piece);
+                    columns.edited(1, offset..offset, piece.len());
+                } else {
+                    let mut end = (offset + random.below(8)).min(line.len());
+                    while !line.is_char_boundary(end) {
+                        end += 1;
+                    }
+                    line.replace_range(offset..end, "");
+                    text.remove(6 + offset..6 + end);
+                    columns.edited(1, offset..end, 0);
+                }
+            }
+
+            let rows = columns.rows(&text, 6..6 + line.len(), 4, width);
+            assert_eq!(*rows, layout::wrap(&line, 4, width), "round {round}");
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! User settings. They're read from `config` in the config directory, which has one setting per
+//! line, and key bindings in sections for each mode (see `keymap`):
+//!
+//! ```text
+//! # comments start with a hash
+//! tab_width = 4
+//! expand_tabs = true
+//! wrap = true
+//! wrap_column = 100
+//! line_numbers = relative
+//!
+//! [keys.edit]
+//! ctrl-x ctrl-s = save
+//! ```
+//!
+//! The file can be shared as is, so a team can keep one set of bindings in a repository and have
+//! everyone link to it.
+
+use std::fs;
+use std::io;
+
+use crate::dirs;
+use crate::gutter::LineNumbers;
+use crate::keymap::{Command, Key, Keymap, Mode};
+
+pub struct Config {
+    /// How many columns a tab stop is.
+    pub tab_width: usize,
+    /// Whether Tab inserts spaces up to the next tab stop instead of a tab character.
+    pub expand_tabs: bool,
+    /// Whether lines that are too long for the screen are wrapped onto the next row, instead of
+    /// going off the edge.
+    pub wrap: bool,
+    /// The column lines are wrapped at, if it's narrower than the screen; 0 to always wrap at the
+    /// edge of the screen.
+    pub wrap_column: usize,
+    /// Which line numbers go in the gutter: `off`, `absolute`, `relative` (to the cursor) or
+    /// `hybrid` (relative, but absolute on the cursor's line).
+    pub line_numbers: LineNumbers,
+    pub keymap: Keymap,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            tab_width: 4,
+            expand_tabs: false,
+            wrap: false,
+            wrap_column: 0,
+            line_numbers: LineNumbers::Absolute,
+            keymap: Keymap::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config file, if there is one. Settings that can't be understood are skipped, and
+    /// what was wrong with them is returned along with the rest of the config.
+    pub fn load() -> (Config, Vec<String>) {
+        let Some(path) = dirs::config_dir().map(|dir| dir.join("config")) else {
+            return (Config::default(), Vec::new());
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(text) => Config::parse(&text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (Config::default(), Vec::new()),
+            Err(e) => (
+                Config::default(),
+                vec![format!("couldn't read {}: {e}", path.display())],
+            ),
+        }
+    }
+
+    fn parse(text: &str) -> (Config, Vec<String>) {
+        let mut config = Config::default();
+        let mut problems = Vec::new();
+        let mut section = Section::Settings;
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let result = if let Some(header) = line.strip_prefix('[') {
+                section = Section::parse(header.trim_end_matches(']').trim());
+                match section {
+                    Section::Unknown(ref problem) => Err(problem.clone()),
+                    _ => Ok(()),
+                }
+            } else {
+                match section {
+                    Section::Settings => match line.split_once('=') {
+                        Some((key, value)) => config.set(key.trim(), value.trim()),
+                        None => Err("expected `setting = value`".to_string()),
+                    },
+                    Section::Keys(mode) => match line.rsplit_once('=') {
+                        Some((keys, command)) => config.bind(mode, keys.trim(), command.trim()),
+                        None => Err("expected `keys = command`".to_string()),
+                    },
+                    // the header was reported already
+                    Section::Unknown(_) => Ok(()),
+                }
+            };
+            if let Err(problem) = result {
+                problems.push(format!("config line {}: {problem}", i + 1));
+            }
+        }
+
+        (config, problems)
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "tab_width" => match value.parse() {
+                Ok(width) if width > 0 => self.tab_width = width,
+                _ => {
+                    return Err(format!(
+                        "tab_width must be a positive number, not `{value}`"
+                    ))
+                }
+            },
+            "expand_tabs" => self.expand_tabs = parse_bool(key, value)?,
+            "wrap" => self.wrap = parse_bool(key, value)?,
+            "wrap_column" => match value.parse() {
+                Ok(column) => self.wrap_column = column,
+                Err(_) => return Err(format!("wrap_column must be a number, not `{value}`")),
+            },
+            "line_numbers" => {
+                self.line_numbers = LineNumbers::from_name(value).ok_or_else(|| {
+                    format!("line_numbers must be off, absolute, relative or hybrid, not `{value}`")
+                })?
+            }
+            _ => return Err(format!("unknown setting `{key}`")),
+        }
+
+        Ok(())
+    }
+
+    fn bind(&mut self, mode: Mode, keys: &str, command: &str) -> Result<(), String> {
+        let keys = Key::parse_sequence(keys).ok_or_else(|| format!("can't read keys `{keys}`"))?;
+        let command = match command {
+            "none" => None,
+            _ => Some(
+                Command::from_name(command)
+                    .ok_or_else(|| format!("unknown command `{command}`"))?,
+            ),
+        };
+        self.keymap.bind(mode, &keys, command);
+
+        Ok(())
+    }
+}
+
+/// The part of the config file a line is in.
+enum Section {
+    /// Before any `[section]` header.
+    Settings,
+    /// `[keys]` (for edit mode) or `[keys.<mode>]`.
+    Keys(Mode),
+    /// A header that didn't make sense, and why.
+    Unknown(String),
+}
+
+impl Section {
+    fn parse(header: &str) -> Section {
+        match header {
+            "keys" => Section::Keys(Mode::Edit),
+            _ => match header.strip_prefix("keys.") {
+                Some(mode) => Mode::from_name(mode).map_or_else(
+                    || Section::Unknown(format!("unknown mode `{mode}`")),
+                    Section::Keys,
+                ),
+                None => Section::Unknown(format!("unknown section `{header}`")),
+            },
+        }
+    }
+}
+
+fn parse_bool(key: &str, value: &str) -> Result<bool, String> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(format!("{key} must be true or false, not `{value}`")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keymap::Lookup;
+
+    fn lookup(config: &Config, mode: Mode, keys: &str) -> Lookup {
+        config
+            .keymap
+            .lookup(mode, &Key::parse_sequence(keys).unwrap())
+    }
+
+    #[test]
+    fn settings() {
+        let (config, problems) = Config::parse(
+            "# a comment\n\
+             tab_width = 8\n\
+             \n  expand_tabs=true  \n\
+             wrap = true\n\
+             wrap_column = 100\n\
+             line_numbers = hybrid\n",
+        );
+        assert_eq!(problems, Vec::<String>::new());
+        assert_eq!(config.tab_width, 8);
+        assert!(config.expand_tabs);
+        assert!(config.wrap);
+        assert_eq!(config.wrap_column, 100);
+        assert_eq!(config.line_numbers, LineNumbers::Hybrid);
+    }
+
+    #[test]
+    fn bindings() {
+        let (config, problems) = Config::parse(
+            "[keys]\n\
+             ctrl-- = undo\n\
+             - = redo\n\
+             shift-tab = insert-tab\n\
+             ctrl-s x = quit\n\
+             ctrl-z = none\n\
+             [keys.prompt]\n\
+             ctrl-g = none\n",
+        );
+        assert_eq!(problems, Vec::<String>::new());
+        assert_eq!(
+            lookup(&config, Mode::Edit, "ctrl--"),
+            Lookup::Command(Command::Undo)
+        );
+        assert_eq!(
+            lookup(&config, Mode::Edit, "-"),
+            Lookup::Command(Command::Redo)
+        );
+        assert_eq!(
+            lookup(&config, Mode::Edit, "backtab"),
+            Lookup::Command(Command::InsertTab)
+        );
+        // the chord took the place of ctrl-s on its own
+        assert_eq!(lookup(&config, Mode::Edit, "ctrl-s"), Lookup::Pending);
+        assert_eq!(
+            lookup(&config, Mode::Edit, "ctrl-s x"),
+            Lookup::Command(Command::Quit)
+        );
+        assert_eq!(lookup(&config, Mode::Edit, "ctrl-z"), Lookup::Unbound);
+        assert_eq!(lookup(&config, Mode::Prompt, "ctrl-g"), Lookup::Unbound);
+        // and the defaults that weren't touched are still there
+        assert_eq!(
+            lookup(&config, Mode::Prompt, "esc"),
+            Lookup::Command(Command::Cancel)
+        );
+    }
+
+    #[test]
+    fn problems() {
+        let (config, problems) = Config::parse(
+            "tab_width = 0\n\
+             tab_width = 2\n\
+             wrap = yes\n\
+             colour = red\n\
+             just some words\n\
+             \n\
+             [keys]\n\
+             hyper-x = save\n\
+             ctrl-q = sulk\n\
+             ctrl-q\n\
+             [keys.visual]\n\
+             ctrl-q = quit\n\
+             [colours]\n",
+        );
+        assert_eq!(
+            problems,
+            [
+                "config line 1: tab_width must be a positive number, not `0`",
+                "config line 3: wrap must be true or false, not `yes`",
+                "config line 4: unknown setting `colour`",
+                "config line 5: expected `setting = value`",
+                "config line 8: can't read keys `hyper-x`",
+                "config line 9: unknown command `sulk`",
+                "config line 10: expected `keys = command`",
+                "config line 11: unknown mode `visual`",
+                "config line 13: unknown section `colours`",
+            ]
+        );
+        // what could be read still was
+        assert_eq!(config.tab_width, 2);
+        assert_eq!(lookup(&config, Mode::Edit, "ctrl-q"), Lookup::Unbound);
+    }
+}
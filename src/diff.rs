@@ -0,0 +1,281 @@
+//! Line diffs, for showing how two versions of a text differ.
+
+use std::fmt::Write;
+
+/// How far apart two texts can be before the diff stops looking for the shortest way between
+/// them, which gets slow, and just shows everything in between as replaced.
+const MAX_DISTANCE: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Lines `old` and `new` up with each other, with as few lines added and removed as possible.
+pub fn diff<'a>(old: &'a str, new: &'a str) -> Vec<Line<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // the start and end are usually the same, and the shortest path only needs finding in between
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut lines: Vec<Line> = old[..prefix].iter().map(|line| Line::Same(line)).collect();
+    let (a, b) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+    match myers(a, b) {
+        Some(middle) => lines.extend(middle),
+        None => {
+            lines.extend(a.iter().map(|line| Line::Removed(line)));
+            lines.extend(b.iter().map(|line| Line::Added(line)));
+        }
+    }
+    lines.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| Line::Same(line)),
+    );
+
+    lines
+}
+
+/// Myers' diff algorithm: finds the shortest edit script from `a` to `b` by trying ever more
+/// edits `d`, following runs of equal lines for free. `v[k]` is how far along `a` the furthest
+/// path with `d` edits got on diagonal `k` (that is, where `x - y == k`). Returns `None` if it
+/// takes more than `MAX_DISTANCE` edits.
+fn myers<'a>(a: &[&'a str], b: &[&'a str]) -> Option<Vec<Line<'a>>> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = (a.len() + b.len()).min(MAX_DISTANCE) as isize;
+    // diagonals go from -max to max, plus one on either side that's read but never written
+    let offset = max + 1;
+    let mut v = vec![0isize; 2 * offset as usize + 1];
+    // the part of `v` that matters before each round, for walking back along the path after
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    let down = |v: &[isize], k: isize, d: isize| {
+        let i = (k + offset) as usize;
+        k == -d || (k != d && v[i - 1] < v[i + 1])
+    };
+
+    let mut found = false;
+    'search: for d in 0..=max {
+        trace.push(v[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            // step down from the diagonal above (adding a line from `b`), or right from the one
+            // below (removing a line from `a`), whichever got further
+            let mut x = if down(&v, k, d) {
+                v[(k + 1 + offset) as usize]
+            } else {
+                v[(k - 1 + offset) as usize] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[(k + offset) as usize] = x;
+
+            if x >= n && y >= m {
+                found = true;
+                break 'search;
+            }
+        }
+    }
+    if !found {
+        return None;
+    }
+
+    let mut lines = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        // `v` starts at diagonal -d - 1
+        let at = |k: isize| v[(k + d + 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            lines.push(Line::Same(a[x as usize - 1]));
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            if x == prev_x {
+                lines.push(Line::Added(b[y as usize - 1]));
+            } else {
+                lines.push(Line::Removed(a[x as usize - 1]));
+            }
+            (x, y) = (prev_x, prev_y);
+        }
+    }
+    lines.reverse();
+
+    Some(lines)
+}
+
+/// Formats `lines` like `diff -u` does, with `context` unchanged lines around each change.
+pub fn unified(lines: &[Line], context: usize) -> String {
+    let changed: Vec<usize> = (0..lines.len())
+        .filter(|&i| !matches!(lines[i], Line::Same(_)))
+        .collect();
+
+    // changes that are close enough together share a hunk
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &i in &changed {
+        let start = i.saturating_sub(context);
+        let end = (i + context + 1).min(lines.len());
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut out = String::new();
+    for (start, end) in hunks {
+        // line numbers in the old and new text where the hunk starts
+        let old_start = lines[..start]
+            .iter()
+            .filter(|line| !matches!(line, Line::Added(_)))
+            .count();
+        let new_start = lines[..start]
+            .iter()
+            .filter(|line| !matches!(line, Line::Removed(_)))
+            .count();
+        let hunk = &lines[start..end];
+        let old_len = hunk
+            .iter()
+            .filter(|line| !matches!(line, Line::Added(_)))
+            .count();
+        let new_len = hunk
+            .iter()
+            .filter(|line| !matches!(line, Line::Removed(_)))
+            .count();
+
+        let _ = writeln!(
+            out,
+            "@@ -{},{old_len} +{},{new_len} @@",
+            old_start + 1,
+            new_start + 1
+        );
+        for line in hunk {
+            let _ = match line {
+                Line::Same(text) => writeln!(out, " {text}"),
+                Line::Removed(text) => writeln!(out, "-{text}"),
+                Line::Added(text) => writeln!(out, "+{text}"),
+            };
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The old and new text that `lines` are a diff between.
+    fn sides(lines: &[Line]) -> (Vec<String>, Vec<String>) {
+        let (mut old, mut new) = (Vec::new(), Vec::new());
+        for line in lines {
+            match *line {
+                Line::Same(text) => {
+                    old.push(text.to_string());
+                    new.push(text.to_string());
+                }
+                Line::Removed(text) => old.push(text.to_string()),
+                Line::Added(text) => new.push(text.to_string()),
+            }
+        }
+        (old, new)
+    }
+
+    #[test]
+    fn one_line_changed() {
+        assert_eq!(
+            diff("a\nb\nc\n", "a\nx\nc\n"),
+            [
+                Line::Same("a"),
+                Line::Removed("b"),
+                Line::Added("x"),
+                Line::Same("c"),
+            ]
+        );
+        assert_eq!(diff("", "a\n"), [Line::Added("a")]);
+        assert_eq!(diff("a\n", ""), [Line::Removed("a")]);
+        assert_eq!(diff("a\nb", "a\nb\n"), [Line::Same("a"), Line::Same("b")]);
+    }
+
+    #[test]
+    fn shortest() {
+        // the example from Myers' paper, which takes five edits
+        let a = ["a", "b", "c", "a", "b", "b", "a"];
+        let b = ["c", "b", "a", "b", "a", "c"];
+        let lines = myers(&a, &b).unwrap();
+        let changed = lines
+            .iter()
+            .filter(|line| !matches!(line, Line::Same(_)))
+            .count();
+        assert_eq!(changed, 5);
+        assert_eq!(
+            sides(&lines),
+            (a.map(String::from).to_vec(), b.map(String::from).to_vec())
+        );
+
+        assert_eq!(myers(&[], &[]), Some(Vec::new()));
+        assert_eq!(myers(&["a"], &[]), Some(vec![Line::Removed("a")]));
+        assert_eq!(myers(&[], &["a"]), Some(vec![Line::Added("a")]));
+    }
+
+    #[test]
+    fn too_far_apart() {
+        let old: String = (0..MAX_DISTANCE).map(|i| format!("old {i}\n")).collect();
+        let new: String = (0..MAX_DISTANCE).map(|i| format!("new {i}\n")).collect();
+        let a: Vec<&str> = old.lines().collect();
+        let b: Vec<&str> = new.lines().collect();
+        assert_eq!(myers(&a, &b), None);
+
+        // everything in between the same start and end shows up as replaced instead
+        let (old, new) = (format!("top\n{old}bottom\n"), format!("top\n{new}bottom\n"));
+        let lines = diff(&old, &new);
+        assert_eq!(lines.len(), 2 * MAX_DISTANCE + 2);
+        assert_eq!(lines[0], Line::Same("top"));
+        assert!(lines[1..=MAX_DISTANCE]
+            .iter()
+            .all(|line| matches!(line, Line::Removed(_))));
+        assert!(lines[MAX_DISTANCE + 1..2 * MAX_DISTANCE + 1]
+            .iter()
+            .all(|line| matches!(line, Line::Added(_))));
+        assert_eq!(lines[2 * MAX_DISTANCE + 1], Line::Same("bottom"));
+    }
+
+    #[test]
+    fn hunks() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let new = "1\ntwo\n3\n4\n5\n6\n7\n8\n9\nten\n";
+        assert_eq!(
+            unified(&diff(old, new), 1),
+            "@@ -1,3 +1,3 @@\n 1\n-2\n+two\n 3\n@@ -9,1 +9,2 @@\n 9\n+ten\n"
+        );
+        // changes with no more than twice the context between them share a hunk
+        assert_eq!(
+            unified(&diff("1\n2\n3\n4\n", "one\n2\n3\nfour\n"), 1),
+            "@@ -1,4 +1,4 @@\n-1\n+one\n 2\n 3\n-4\n+four\n"
+        );
+        assert_eq!(unified(&diff(old, old), 3), "");
+    }
+}
@@ -0,0 +1,34 @@
+//! Where edythe keeps its own files, following the XDG base directory spec.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// `$XDG_STATE_HOME/edythe`, or `~/.local/state/edythe` if that isn't set. This is for things
+/// that should survive a restart but aren't worth backing up, like undo history.
+pub fn state_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        // the spec says relative paths are invalid and should be ignored
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| Some(PathBuf::from(env::var_os("HOME")?).join(".local/state")))?;
+
+    Some(base.join("edythe"))
+}
+
+/// `$XDG_CONFIG_HOME/edythe`, or `~/.config/edythe` if that isn't set.
+pub fn config_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| Some(PathBuf::from(env::var_os("HOME")?).join(".config")))?;
+
+    Some(base.join("edythe"))
+}
+
+/// Turns `path` into a single file name by escaping its `/`s, for keeping something about a file
+/// under a name that still shows which file it's about.
+pub fn escape_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace('%', "%25")
+        .replace('/', "%2F")
+}
@@ -0,0 +1,380 @@
+//! Character encodings for files that aren't plain UTF-8. Buffers always hold UTF-8; a file is
+//! decoded when it's opened and encoded back into whatever it was in when it's saved.
+
+use std::fmt;
+
+/// What Windows-1252 has in 0x80..=0x9F, where Latin-1 has control characters. The five bytes
+/// Windows-1252 leaves undefined are kept as those control characters, like browsers do, so they
+/// survive a round trip.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20ac}', '\u{81}', '\u{201a}', '\u{192}', '\u{201e}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2c6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8d}', '\u{17d}', '\u{8f}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201c}', '\u{201d}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2dc}', '\u{2122}', '\u{161}', '\u{203a}', '\u{153}', '\u{9d}', '\u{17e}', '\u{178}',
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    /// UTF-8 that starts with a byte order mark.
+    Utf8Bom,
+    /// UTF-16, little endian, with a byte order mark.
+    Utf16Le,
+    /// UTF-16, big endian, with a byte order mark.
+    Utf16Be,
+    /// ISO-8859-1, where every byte is the code point with the same number.
+    Latin1,
+    Windows1252,
+}
+
+impl Encoding {
+    /// Every encoding there is, for listing them.
+    pub const ALL: [Encoding; 6] = [
+        Encoding::Utf8,
+        Encoding::Utf8Bom,
+        Encoding::Utf16Le,
+        Encoding::Utf16Be,
+        Encoding::Latin1,
+        Encoding::Windows1252,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "utf-8",
+            Encoding::Utf8Bom => "utf-8-bom",
+            Encoding::Utf16Le => "utf-16le",
+            Encoding::Utf16Be => "utf-16be",
+            Encoding::Latin1 => "latin-1",
+            Encoding::Windows1252 => "windows-1252",
+        }
+    }
+
+    /// Looks up an encoding by name, ignoring case, and accepting a few other common names.
+    pub fn from_name(name: &str) -> Option<Encoding> {
+        let name = name.trim().to_ascii_lowercase();
+        let encoding = match name.as_str() {
+            "utf8" => Encoding::Utf8,
+            "utf-16" | "utf16" | "utf16le" => Encoding::Utf16Le,
+            "utf16be" => Encoding::Utf16Be,
+            "latin1" | "iso-8859-1" | "iso8859-1" => Encoding::Latin1,
+            "cp1252" | "windows1252" => Encoding::Windows1252,
+            _ => return Encoding::ALL.into_iter().find(|e| e.name() == name),
+        };
+
+        Some(encoding)
+    }
+
+    /// Works out what `bytes` are most likely encoded in. A byte order mark settles it, and
+    /// otherwise it's UTF-8 if that's valid, and one of the 8-bit encodings if not.
+    pub fn detect(bytes: &[u8]) -> Encoding {
+        if bytes.starts_with(&[0xef, 0xbb, 0xbf]) {
+            Encoding::Utf8Bom
+        } else if bytes.starts_with(&[0xff, 0xfe]) {
+            Encoding::Utf16Le
+        } else if bytes.starts_with(&[0xfe, 0xff]) {
+            Encoding::Utf16Be
+        } else if std::str::from_utf8(bytes).is_ok() {
+            Encoding::Utf8
+        } else if bytes.iter().any(|b| (0x80..=0x9f).contains(b)) {
+            // these are control characters in Latin-1 that nobody puts in text, but printable
+            // ones (curly quotes, the euro sign, ...) in Windows-1252
+            Encoding::Windows1252
+        } else {
+            // which is the same as Windows-1252 for all the bytes that are there
+            Encoding::Latin1
+        }
+    }
+
+    /// The byte order mark that files in this encoding start with, if they have one.
+    pub fn bom(self) -> &'static [u8] {
+        match self {
+            Encoding::Utf8Bom => &[0xef, 0xbb, 0xbf],
+            Encoding::Utf16Le => &[0xff, 0xfe],
+            Encoding::Utf16Be => &[0xfe, 0xff],
+            Encoding::Utf8 | Encoding::Latin1 | Encoding::Windows1252 => &[],
+        }
+    }
+
+    /// Decodes `bytes`, which include the byte order mark if there is one. Fails if they aren't
+    /// valid in this encoding, which only the UTF ones can be.
+    pub fn decode(self, bytes: &[u8]) -> Result<String, DecodeError> {
+        let bytes = bytes.strip_prefix(self.bom()).unwrap_or(bytes);
+        match self {
+            Encoding::Utf8 | Encoding::Utf8Bom => String::from_utf8(bytes.to_vec())
+                .map_err(|e| DecodeError(e.utf8_error().valid_up_to())),
+            Encoding::Utf16Le | Encoding::Utf16Be => {
+                if !bytes.len().is_multiple_of(2) {
+                    return Err(DecodeError(bytes.len() - 1));
+                }
+                let units = bytes.chunks_exact(2).map(|pair| {
+                    let pair = [pair[0], pair[1]];
+                    if self == Encoding::Utf16Le {
+                        u16::from_le_bytes(pair)
+                    } else {
+                        u16::from_be_bytes(pair)
+                    }
+                });
+                let mut text = String::with_capacity(bytes.len() / 2);
+                let mut offset = 0;
+                for c in char::decode_utf16(units) {
+                    // an unpaired surrogate
+                    let c = c.map_err(|_| DecodeError(offset))?;
+                    offset += c.len_utf16() * 2;
+                    text.push(c);
+                }
+                Ok(text)
+            }
+            Encoding::Latin1 => Ok(bytes.iter().map(|&b| char::from(b)).collect()),
+            Encoding::Windows1252 => Ok(bytes
+                .iter()
+                .map(|&b| match b {
+                    0x80..=0x9f => WINDOWS_1252_HIGH[usize::from(b - 0x80)],
+                    _ => char::from(b),
+                })
+                .collect()),
+        }
+    }
+
+    /// Decodes `bytes` like `decode`, but with anything invalid replaced by U+FFFD instead of
+    /// failing.
+    pub fn decode_lossy(self, bytes: &[u8]) -> String {
+        let bytes = bytes.strip_prefix(self.bom()).unwrap_or(bytes);
+        match self {
+            Encoding::Utf8 | Encoding::Utf8Bom => String::from_utf8_lossy(bytes).into_owned(),
+            Encoding::Utf16Le | Encoding::Utf16Be => {
+                let mut units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|pair| {
+                        let pair = [pair[0], pair[1]];
+                        if self == Encoding::Utf16Le {
+                            u16::from_le_bytes(pair)
+                        } else {
+                            u16::from_be_bytes(pair)
+                        }
+                    })
+                    .collect();
+                // an odd byte at the end
+                if !bytes.len().is_multiple_of(2) {
+                    units.push(0xfffd);
+                }
+                String::from_utf16_lossy(&units)
+            }
+            // every byte means something in these
+            Encoding::Latin1 | Encoding::Windows1252 => {
+                self.decode(bytes).expect("8-bit encodings always decode")
+            }
+        }
+    }
+
+    /// Encodes `text` onto the end of `out`, without a byte order mark. Fails on the first
+    /// character that can't be encoded, leaving `out` with whatever came before it.
+    pub fn encode(self, text: &str, out: &mut Vec<u8>) -> Result<(), char> {
+        match self {
+            Encoding::Utf8 | Encoding::Utf8Bom => out.extend_from_slice(text.as_bytes()),
+            Encoding::Utf16Le => {
+                for unit in text.encode_utf16() {
+                    out.extend_from_slice(&unit.to_le_bytes());
+                }
+            }
+            Encoding::Utf16Be => {
+                for unit in text.encode_utf16() {
+                    out.extend_from_slice(&unit.to_be_bytes());
+                }
+            }
+            Encoding::Latin1 => {
+                for c in text.chars() {
+                    out.push(u8::try_from(c).map_err(|_| c)?);
+                }
+            }
+            Encoding::Windows1252 => {
+                for c in text.chars() {
+                    let byte = match u8::try_from(c) {
+                        // the bytes that aren't what they are in Latin-1
+                        Ok(0x80..=0x9f) => None,
+                        Ok(b) => Some(b),
+                        Err(_) => None,
+                    };
+                    let byte = byte.or_else(|| {
+                        let i = WINDOWS_1252_HIGH.iter().position(|&high| high == c)?;
+                        Some(0x80 + i as u8)
+                    });
+                    out.push(byte.ok_or(c)?);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Bytes that aren't valid in the encoding they were decoded with, at this offset.
+#[derive(Debug)]
+pub struct DecodeError(pub usize);
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid byte sequence at offset {}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// What a file with `text` in `encoding` holds, byte order mark and all.
+    fn file(encoding: Encoding, text: &str) -> Vec<u8> {
+        let mut bytes = encoding.bom().to_vec();
+        encoding.encode(text, &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn names() {
+        for encoding in Encoding::ALL {
+            assert_eq!(Encoding::from_name(encoding.name()), Some(encoding));
+        }
+        assert_eq!(Encoding::from_name(" UTF8 "), Some(Encoding::Utf8));
+        assert_eq!(Encoding::from_name("cp1252"), Some(Encoding::Windows1252));
+        assert_eq!(Encoding::from_name("ebcdic"), None);
+    }
+
+    #[test]
+    fn detect() {
+        assert_eq!(Encoding::detect(b""), Encoding::Utf8);
+        assert_eq!(Encoding::detect("h\u{e9}llo".as_bytes()), Encoding::Utf8);
+        assert_eq!(Encoding::detect(b"\xef\xbb\xbfhi"), Encoding::Utf8Bom);
+        assert_eq!(Encoding::detect(b"\xff\xfeh\0i\0"), Encoding::Utf16Le);
+        assert_eq!(Encoding::detect(b"\xfe\xff\0h\0i"), Encoding::Utf16Be);
+        // curly quotes only exist in Windows-1252
+        assert_eq!(Encoding::detect(b"\x93hi\x94"), Encoding::Windows1252);
+        assert_eq!(Encoding::detect(b"h\xe9llo"), Encoding::Latin1);
+    }
+
+    #[test]
+    fn round_trips() {
+        let texts = [
+            ("hello\r\nworld\n", &Encoding::ALL[..]),
+            ("caf\u{e9} \u{fc}ber \u{ff}", &Encoding::ALL[..]),
+            (
+                "\u{20ac}5 \u{2018}quoted\u{2019} \u{2026}",
+                &[
+                    Encoding::Utf8,
+                    Encoding::Utf8Bom,
+                    Encoding::Utf16Le,
+                    Encoding::Utf16Be,
+                    Encoding::Windows1252,
+                ],
+            ),
+            (
+                "\u{65e5}\u{672c} \u{1f600} \u{10ffff}",
+                &[
+                    Encoding::Utf8,
+                    Encoding::Utf8Bom,
+                    Encoding::Utf16Le,
+                    Encoding::Utf16Be,
+                ],
+            ),
+        ];
+
+        for (text, encodings) in texts {
+            for &encoding in encodings {
+                let bytes = file(encoding, text);
+                assert_eq!(encoding.decode(&bytes).unwrap(), text, "{encoding}");
+                assert_eq!(encoding.decode_lossy(&bytes), text, "{encoding}");
+                if !encoding.bom().is_empty() {
+                    assert_eq!(Encoding::detect(&bytes), encoding);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn utf16_surrogate_pairs() {
+        // U+1F600 is D83D DE00 in UTF-16
+        assert_eq!(
+            file(Encoding::Utf16Le, "\u{1f600}"),
+            b"\xff\xfe\x3d\xd8\x00\xde"
+        );
+        assert_eq!(
+            file(Encoding::Utf16Be, "\u{1f600}"),
+            b"\xfe\xff\xd8\x3d\xde\x00"
+        );
+        assert_eq!(
+            Encoding::Utf16Le
+                .decode(b"\xff\xfea\0\x3d\xd8\x00\xde")
+                .unwrap(),
+            "a\u{1f600}"
+        );
+    }
+
+    #[test]
+    fn utf16_errors() {
+        // a high surrogate with nothing after it
+        let unpaired = b"\xff\xfea\0\x3d\xd8b\0";
+        assert_eq!(Encoding::Utf16Le.decode(unpaired).unwrap_err().0, 2);
+        assert_eq!(Encoding::Utf16Le.decode_lossy(unpaired), "a\u{fffd}b");
+
+        let odd = b"\xfe\xff\0a\0";
+        assert_eq!(Encoding::Utf16Be.decode(odd).unwrap_err().0, 2);
+        assert_eq!(Encoding::Utf16Be.decode_lossy(odd), "a\u{fffd}");
+    }
+
+    #[test]
+    fn utf8_errors() {
+        assert_eq!(Encoding::Utf8.decode(b"ab\xffc").unwrap_err().0, 2);
+        assert_eq!(Encoding::Utf8.decode_lossy(b"ab\xffc"), "ab\u{fffd}c");
+        // the byte order mark isn't part of the text
+        assert_eq!(Encoding::Utf8Bom.decode(b"\xef\xbb\xbfab").unwrap(), "ab");
+    }
+
+    #[test]
+    fn every_byte_round_trips() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        for encoding in [Encoding::Latin1, Encoding::Windows1252] {
+            let text = encoding.decode(&bytes).unwrap();
+            assert_eq!(text.chars().count(), 256);
+            let mut out = Vec::new();
+            encoding.encode(&text, &mut out).unwrap();
+            assert_eq!(out, bytes, "{encoding}");
+        }
+    }
+
+    #[test]
+    fn windows_1252() {
+        let decoded = Encoding::Windows1252.decode(b"\x80\x85\x8a\x9f").unwrap();
+        assert_eq!(decoded, "\u{20ac}\u{2026}\u{160}\u{178}");
+
+        // the bytes it leaves undefined come back as the Latin-1 control characters and go out as
+        // the same bytes
+        let undefined = b"\x81\x8d\x8f\x90\x9d";
+        let decoded = Encoding::Windows1252.decode(undefined).unwrap();
+        assert_eq!(decoded, "\u{81}\u{8d}\u{8f}\u{90}\u{9d}");
+        let mut out = Vec::new();
+        Encoding::Windows1252.encode(&decoded, &mut out).unwrap();
+        assert_eq!(out, undefined);
+
+        // the other Latin-1 control characters have no byte of their own
+        let mut out = Vec::new();
+        assert_eq!(
+            Encoding::Windows1252.encode("a\u{80}", &mut out),
+            Err('\u{80}')
+        );
+        assert_eq!(out, b"a");
+    }
+
+    #[test]
+    fn latin1_cant_encode_everything() {
+        let mut out = Vec::new();
+        assert_eq!(
+            Encoding::Latin1.encode("\u{ff}\u{20ac}", &mut out),
+            Err('\u{20ac}')
+        );
+        assert_eq!(out, b"\xff");
+    }
+}
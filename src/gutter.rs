@@ -0,0 +1,153 @@
+//! The gutter down the left side of the screen, with line numbers and signs. A sign is a mark
+//! next to a line that something in the editor wants to point out; anything can put signs there
+//! under a name of its own, and replace or clear them without touching anyone else's.
+
+use std::collections::HashMap;
+
+use crossterm::style::{ContentStyle, Stylize};
+
+use crate::render::Frame;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineNumbers {
+    Off,
+    Absolute,
+    /// How far each line is from the cursor, to see how many lines up or down to move.
+    Relative,
+    /// Relative, except for the cursor's line, which gets its actual number.
+    Hybrid,
+}
+
+impl LineNumbers {
+    pub const ALL: [LineNumbers; 4] = [
+        LineNumbers::Off,
+        LineNumbers::Absolute,
+        LineNumbers::Relative,
+        LineNumbers::Hybrid,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LineNumbers::Off => "off",
+            LineNumbers::Absolute => "absolute",
+            LineNumbers::Relative => "relative",
+            LineNumbers::Hybrid => "hybrid",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<LineNumbers> {
+        LineNumbers::ALL
+            .into_iter()
+            .find(|numbers| numbers.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sign {
+    /// What's shown, which has to fit in one cell.
+    pub symbol: char,
+    pub style: ContentStyle,
+    /// Only one sign fits next to a line, so when there's more than one, the one with the highest
+    /// priority is shown.
+    pub priority: u8,
+}
+
+pub struct Gutter {
+    pub numbers: LineNumbers,
+    /// The signs, by the name of what put them there and then by line. A name with no signs
+    /// still keeps the column for them, see `set_signs`.
+    signs: HashMap<&'static str, HashMap<usize, Sign>>,
+}
+
+impl Gutter {
+    pub fn new(numbers: LineNumbers) -> Self {
+        Self {
+            numbers,
+            signs: HashMap::new(),
+        }
+    }
+
+    /// Replaces all the signs `source` put up with `signs`. The column for signs stays even if
+    /// that's none, for a source that only puts up signs for the lines on screen, so the column
+    /// doesn't come and go as lines with signs are scrolled by.
+    pub fn set_signs(
+        &mut self,
+        source: &'static str,
+        signs: impl IntoIterator<Item = (usize, Sign)>,
+    ) {
+        self.signs.insert(source, signs.into_iter().collect());
+    }
+
+    /// Takes away all the signs `source` put up.
+    pub fn clear_signs(&mut self, source: &'static str) {
+        self.signs.remove(source);
+    }
+
+    /// The sign that's shown next to `line`, if it has any.
+    fn sign(&self, line: usize) -> Option<&Sign> {
+        self.signs
+            .values()
+            .filter_map(|signs| signs.get(&line))
+            .max_by_key(|sign| sign.priority)
+    }
+
+    /// Whether there's a column for signs, which is while anything has signs up, or is keeping
+    /// room for them.
+    fn has_signs(&self) -> bool {
+        !self.signs.is_empty()
+    }
+
+    /// How many columns the gutter takes up next to a buffer with `line_count` lines. The numbers
+    /// are as wide as the widest one, and the column for signs is only there while it's wanted.
+    pub fn width(&self, line_count: usize) -> usize {
+        let signs = usize::from(self.has_signs());
+        let numbers = match self.numbers {
+            LineNumbers::Off => 0,
+            _ => line_count.to_string().len(),
+        };
+
+        match signs + numbers {
+            0 => 0,
+            // and a space to keep it apart from the text
+            width => width + 1,
+        }
+    }
+
+    /// Draws the gutter on row `y` for `line`, or leaves it blank if the row is a continuation of
+    /// a wrapped line.
+    pub fn draw(
+        &self,
+        frame: &mut Frame,
+        y: usize,
+        line: Option<usize>,
+        cursor: usize,
+        line_count: usize,
+    ) {
+        let Some(line) = line else {
+            return;
+        };
+
+        let mut x = 0;
+        if self.has_signs() {
+            if let Some(sign) = self.sign(line) {
+                frame.set(0, y, sign.symbol.encode_utf8(&mut [0; 4]), sign.style);
+            }
+            x += 1;
+        }
+
+        let number = match self.numbers {
+            LineNumbers::Off => return,
+            LineNumbers::Absolute => line + 1,
+            LineNumbers::Relative => line.abs_diff(cursor),
+            LineNumbers::Hybrid if line == cursor => line + 1,
+            LineNumbers::Hybrid => line.abs_diff(cursor),
+        };
+        let style = if line == cursor {
+            ContentStyle::new()
+        } else {
+            ContentStyle::new().dim()
+        };
+        let digits = line_count.to_string().len();
+        frame.put_str(x, y, &format!("{number:>digits$}"), style);
+    }
+}
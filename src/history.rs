@@ -0,0 +1,683 @@
+//! Undo/redo. Every change to a buffer is recorded as an `Edit`, and edits that were typed in one
+//! go are grouped together so that a single undo takes back a whole word instead of one letter.
+//! The groups are kept in a tree (see `History`) so no edit is ever lost to undo.
+
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Typing after a pause this long starts a new undo step even in the middle of a word.
+const IDLE: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditKind {
+    Insert,
+    Delete,
+}
+
+/// One change to the text: `text` was inserted at, or deleted from, `offset`.
+#[derive(Debug, Clone)]
+pub struct Edit {
+    pub kind: EditKind,
+    pub offset: usize,
+    pub text: String,
+    /// Where the cursor was right before the edit. Undo puts it back there.
+    pub cursor: usize,
+}
+
+impl Edit {
+    /// Where the cursor ends up right after the edit. Redo puts it there.
+    pub fn cursor_after(&self) -> usize {
+        match self.kind {
+            EditKind::Insert => self.offset + self.text.len(),
+            EditKind::Delete => self.offset,
+        }
+    }
+
+    /// Tries to fold `next` into this edit, which works if it picks up exactly where this one
+    /// left off: typing at the end of an insert, backspacing from the start of a delete or
+    /// deleting forward from the same spot.
+    fn merge(&mut self, next: &Edit) -> bool {
+        match (self.kind, next.kind) {
+            (EditKind::Insert, EditKind::Insert)
+                if next.offset == self.offset + self.text.len() =>
+            {
+                self.text.push_str(&next.text);
+            }
+            (EditKind::Delete, EditKind::Delete)
+                if next.offset + next.text.len() == self.offset =>
+            {
+                self.text.insert_str(0, &next.text);
+                self.offset = next.offset;
+            }
+            (EditKind::Delete, EditKind::Delete) if next.offset == self.offset => {
+                self.text.push_str(&next.text);
+            }
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+/// The edits that one undo or redo takes back or re-applies, in the order they were made.
+pub type Group = Vec<Edit>;
+
+/// A node in the undo tree: the text as it is after applying `edits` to the parent's text.
+struct State {
+    parent: usize,
+    edits: Group,
+    /// When the last of `edits` was made.
+    time: SystemTime,
+    /// The child that redo goes to, which is whichever one was visited last.
+    redo: Option<usize>,
+}
+
+/// What it takes to get from one state of the text to another.
+pub enum Step<'a> {
+    /// Undo these edits, last one first.
+    Revert(&'a Group),
+    /// Redo these edits, first one first.
+    Apply(&'a Group),
+}
+
+/// The edit history, kept as a tree: undoing and then typing starts a new branch instead of
+/// throwing the undone edits away, so every state the text has ever been in can be reached again.
+pub struct History {
+    /// `states[0]` is the text as it was loaded. States are never removed, so their order in
+    /// here is the order they were made in.
+    states: Vec<State>,
+    /// The state the text is currently in.
+    current: usize,
+    /// The state the text was in when it was last loaded from or saved to disk.
+    saved: usize,
+    last_edit: Option<Instant>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            states: vec![State {
+                parent: 0,
+                edits: Vec::new(),
+                time: SystemTime::now(),
+                redo: None,
+            }],
+            current: 0,
+            saved: 0,
+            last_edit: None,
+        }
+    }
+}
+
+impl History {
+    /// Records an edit that was just applied to the buffer. It either extends the current state,
+    /// if that's the newest one and the edit continues it, or becomes a new child of it.
+    pub fn record(&mut self, edit: Edit) {
+        let now = Instant::now();
+        let idle = self
+            .last_edit
+            .is_none_or(|last| now.duration_since(last) > IDLE);
+        self.last_edit = Some(now);
+
+        let current = self.current;
+        if !idle && current != 0 && current == self.states.len() - 1 {
+            let state = &mut self.states[current];
+            if let Some(last) = state.edits.last_mut() {
+                if !starts_word(last, &edit) && last.merge(&edit) {
+                    state.time = SystemTime::now();
+                    return;
+                }
+            }
+        }
+
+        self.push(vec![edit]);
+    }
+
+    /// Records edits that were just applied to the buffer together as a step of their own, which
+    /// later edits don't get merged into.
+    pub fn record_group(&mut self, edits: Group) {
+        self.push(edits);
+        self.seal();
+    }
+
+    /// Adds a new state with `edits` as a child of the current one, and makes it current.
+    fn push(&mut self, edits: Group) {
+        let parent = self.current;
+        self.states.push(State {
+            parent,
+            edits,
+            time: SystemTime::now(),
+            redo: None,
+        });
+        self.current = self.states.len() - 1;
+        self.states[parent].redo = Some(self.current);
+    }
+
+    /// Ends the current undo step, so the next edit starts a new one.
+    pub fn seal(&mut self) {
+        self.last_edit = None;
+    }
+
+    /// Whether the text is in the state it was last loaded from or saved to disk in.
+    pub fn is_saved(&self) -> bool {
+        self.current == self.saved
+    }
+
+    /// Remembers that the text in its current state is what's on disk now.
+    pub fn mark_saved(&mut self) {
+        self.seal();
+        self.saved = self.current;
+    }
+
+    /// Goes back to the parent of the current state.
+    pub fn undo(&mut self) -> Vec<Step<'_>> {
+        let parent = self.states[self.current].parent;
+        self.travel(parent)
+    }
+
+    /// Goes forward to the child of the current state that was visited last.
+    pub fn redo(&mut self) -> Vec<Step<'_>> {
+        match self.states[self.current].redo {
+            Some(child) => self.travel(child),
+            None => Vec::new(),
+        }
+    }
+
+    /// Goes to the state that was made right before the current one, whichever branch it's on.
+    pub fn earlier(&mut self) -> Vec<Step<'_>> {
+        self.travel(self.current.saturating_sub(1))
+    }
+
+    /// Goes to the state that was made right after the current one, whichever branch it's on.
+    pub fn later(&mut self) -> Vec<Step<'_>> {
+        let next = (self.current + 1).min(self.states.len() - 1);
+        self.travel(next)
+    }
+
+    /// Goes to the text as it was `ago` before the current state was made.
+    pub fn earlier_by(&mut self, ago: Duration) -> Vec<Step<'_>> {
+        let time = self.states[self.current].time;
+        let target = time.checked_sub(ago).map_or(0, |t| self.newest_at(t));
+        self.travel(target)
+    }
+
+    /// Goes to the text as it was `later` after the current state was made.
+    pub fn later_by(&mut self, later: Duration) -> Vec<Step<'_>> {
+        let time = self.states[self.current].time;
+        let target = time
+            .checked_add(later)
+            .map_or(self.states.len() - 1, |t| self.newest_at(t));
+        self.travel(target)
+    }
+
+    /// The newest state that already existed at `time`.
+    fn newest_at(&self, time: SystemTime) -> usize {
+        // only the newest state ever gets its time bumped, so states are sorted by time
+        self.states
+            .partition_point(|state| state.time <= time)
+            .saturating_sub(1)
+    }
+
+    /// Moves to `target` by undoing up to the closest state it shares with the current one and
+    /// then redoing down to it. Returns the steps the caller has to apply to the text.
+    fn travel(&mut self, target: usize) -> Vec<Step<'_>> {
+        self.seal();
+
+        let mut up = self.ancestors(self.current);
+        let mut down = self.ancestors(target);
+        // drop the path they share from the root down
+        while up.len() > 1 && down.len() > 1 && up[up.len() - 2] == down[down.len() - 2] {
+            up.pop();
+            down.pop();
+        }
+        // the shared state itself is the last one in both; it doesn't need to be touched
+        up.pop();
+        down.pop();
+        down.reverse();
+
+        // remember the way back down so redo retraces it
+        for &state in up.iter().chain(&down) {
+            let parent = self.states[state].parent;
+            self.states[parent].redo = Some(state);
+        }
+        self.current = target;
+
+        up.iter()
+            .map(|&state| Step::Revert(&self.states[state].edits))
+            .chain(
+                down.iter()
+                    .map(|&state| Step::Apply(&self.states[state].edits)),
+            )
+            .collect()
+    }
+
+    /// `state` and all the states above it, ending with the root.
+    fn ancestors(&self, mut state: usize) -> Vec<usize> {
+        let mut path = vec![state];
+        while state != 0 {
+            state = self.states[state].parent;
+            path.push(state);
+        }
+
+        path
+    }
+}
+
+/// The history is stored as a flat list of little-endian numbers and length-prefixed strings:
+///
+/// ```text
+/// states, saved
+/// for each state: parent, time (secs, nanos), redo (u64::MAX for none), edits
+///     for each edit: kind (0 insert, 1 delete), offset, cursor, text length, text
+/// ```
+///
+/// The text on disk is what the `saved` state holds, so that's the state a loaded history
+/// starts out in.
+impl History {
+    pub fn write_to(&self, out: &mut impl Write) -> io::Result<()> {
+        write_u64(out, self.states.len() as u64)?;
+        write_u64(out, self.saved as u64)?;
+        for state in &self.states {
+            let time = state.time.duration_since(UNIX_EPOCH).unwrap_or_default();
+            write_u64(out, state.parent as u64)?;
+            write_u64(out, time.as_secs())?;
+            write_u64(out, time.subsec_nanos().into())?;
+            write_u64(out, state.redo.map_or(u64::MAX, |redo| redo as u64))?;
+            write_u64(out, state.edits.len() as u64)?;
+            for edit in &state.edits {
+                let kind = match edit.kind {
+                    EditKind::Insert => 0,
+                    EditKind::Delete => 1,
+                };
+                write_u64(out, kind)?;
+                write_u64(out, edit.offset as u64)?;
+                write_u64(out, edit.cursor as u64)?;
+                write_u64(out, edit.text.len() as u64)?;
+                out.write_all(edit.text.as_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn read_from(input: &mut impl Read) -> io::Result<History> {
+        let count = read_index(input, usize::MAX)?;
+        if count == 0 {
+            return Err(invalid("undo history has no states"));
+        }
+        let saved = read_index(input, count - 1)?;
+
+        let mut states = Vec::new();
+        for i in 0..count {
+            // the root is its own parent; everything else comes after its parent
+            let parent = read_index(input, i.saturating_sub(1))?;
+            let secs = read_u64(input)?;
+            let nanos = read_u64(input)?;
+            let time = u32::try_from(nanos)
+                .ok()
+                .and_then(|nanos| UNIX_EPOCH.checked_add(Duration::new(secs, nanos)))
+                .ok_or_else(|| invalid("undo history has a bad timestamp"))?;
+            let redo = match read_u64(input)? {
+                u64::MAX => None,
+                redo => Some(checked_index(redo, count - 1)?),
+            };
+
+            let mut edits = Vec::new();
+            for _ in 0..read_index(input, usize::MAX)? {
+                let kind = match read_u64(input)? {
+                    0 => EditKind::Insert,
+                    1 => EditKind::Delete,
+                    _ => return Err(invalid("undo history has an unknown kind of edit")),
+                };
+                let offset = read_index(input, usize::MAX)?;
+                let cursor = read_index(input, usize::MAX)?;
+                let len = read_u64(input)?;
+                let mut text = Vec::new();
+                input.take(len).read_to_end(&mut text)?;
+                if text.len() as u64 != len {
+                    return Err(io::ErrorKind::UnexpectedEof.into());
+                }
+                let text = String::from_utf8(text)
+                    .map_err(|_| invalid("undo history has text that isn't UTF-8"))?;
+
+                edits.push(Edit {
+                    kind,
+                    offset,
+                    text,
+                    cursor,
+                });
+            }
+
+            states.push(State {
+                parent,
+                edits,
+                time,
+                redo,
+            });
+        }
+
+        Ok(History {
+            states,
+            current: saved,
+            saved,
+            last_edit: None,
+        })
+    }
+}
+
+fn write_u64(out: &mut impl Write, n: u64) -> io::Result<()> {
+    out.write_all(&n.to_le_bytes())
+}
+
+fn read_u64(input: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    input.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Reads a number that has to be at most `max` to make sense.
+fn read_index(input: &mut impl Read, max: usize) -> io::Result<usize> {
+    checked_index(read_u64(input)?, max)
+}
+
+fn checked_index(n: u64, max: usize) -> io::Result<usize> {
+    usize::try_from(n)
+        .ok()
+        .filter(|&n| n <= max)
+        .ok_or_else(|| invalid("undo history index out of range"))
+}
+
+fn invalid(reason: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason)
+}
+
+/// Whether `next` starts a new word relative to `last`, which is where undo steps get split:
+/// typing "hello world" undoes as "world" and then "hello ".
+fn starts_word(last: &Edit, next: &Edit) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    // the character `next` touches that's nearest to `last`
+    let (prev, new) = match next.kind {
+        EditKind::Insert => (last.text.chars().next_back(), next.text.chars().next()),
+        EditKind::Delete if next.offset < last.offset => {
+            (last.text.chars().next(), next.text.chars().next_back())
+        }
+        EditKind::Delete => (last.text.chars().next_back(), next.text.chars().next()),
+    };
+
+    match (prev, new) {
+        (Some(prev), Some(new)) => !is_word(prev) && is_word(new),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A history and the text it's the history of, kept in step with each other.
+    struct Text {
+        history: History,
+        text: String,
+    }
+
+    impl Text {
+        fn new(text: &str) -> Self {
+            Text {
+                history: History::default(),
+                text: text.to_string(),
+            }
+        }
+
+        /// Types `typed` a character at a time at `offset`.
+        fn type_at(&mut self, mut offset: usize, typed: &str) {
+            for c in typed.chars() {
+                self.text.insert(offset, c);
+                self.history.record(Edit {
+                    kind: EditKind::Insert,
+                    offset,
+                    text: c.to_string(),
+                    cursor: offset,
+                });
+                offset += c.len_utf8();
+            }
+        }
+
+        /// Deletes the character at `offset`, which is what backspace does before the cursor and
+        /// delete does under it.
+        fn delete_at(&mut self, offset: usize, cursor: usize) {
+            let c = self.text.remove(offset);
+            self.history.record(Edit {
+                kind: EditKind::Delete,
+                offset,
+                text: c.to_string(),
+                cursor,
+            });
+        }
+
+        fn undo(&mut self) -> &str {
+            apply(&mut self.text, self.history.undo());
+            &self.text
+        }
+
+        fn redo(&mut self) -> &str {
+            apply(&mut self.text, self.history.redo());
+            &self.text
+        }
+    }
+
+    /// Does to `text` what `steps` say to.
+    fn apply(text: &mut String, steps: Vec<Step>) {
+        for step in steps {
+            match step {
+                Step::Revert(edits) => {
+                    for edit in edits.iter().rev() {
+                        match edit.kind {
+                            EditKind::Insert => {
+                                text.replace_range(edit.offset..edit.cursor_after(), "")
+                            }
+                            EditKind::Delete => text.insert_str(edit.offset, &edit.text),
+                        }
+                    }
+                }
+                Step::Apply(edits) => {
+                    for edit in edits {
+                        match edit.kind {
+                            EditKind::Insert => text.insert_str(edit.offset, &edit.text),
+                            EditKind::Delete => {
+                                text.replace_range(edit.offset..edit.offset + edit.text.len(), "")
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn words() {
+        let mut text = Text::new("");
+        text.type_at(0, "hello world");
+        assert_eq!(text.undo(), "hello ");
+        assert_eq!(text.undo(), "");
+        // there's nothing before the start
+        assert_eq!(text.undo(), "");
+        assert_eq!(text.redo(), "hello ");
+        assert_eq!(text.redo(), "hello world");
+        assert_eq!(text.redo(), "hello world");
+    }
+
+    #[test]
+    fn typing_somewhere_else() {
+        let mut text = Text::new("");
+        text.type_at(0, "abc");
+        text.type_at(1, "x");
+        assert_eq!(text.text, "axbc");
+        assert_eq!(text.undo(), "abc");
+        assert_eq!(text.undo(), "");
+    }
+
+    #[test]
+    fn idle_timeout() {
+        let mut text = Text::new("");
+        text.type_at(0, "ab");
+        // a pause in the middle of a word still starts a new step
+        text.history.last_edit = Some(Instant::now() - IDLE - Duration::from_millis(10));
+        text.type_at(2, "cd");
+        assert_eq!(text.undo(), "ab");
+        assert_eq!(text.undo(), "");
+
+        // and so does sealing it, which is what moving the cursor does
+        text.type_at(0, "ab");
+        text.history.seal();
+        text.type_at(2, "cd");
+        assert_eq!(text.undo(), "ab");
+    }
+
+    #[test]
+    fn backspace() {
+        let mut text = Text::new("foo bar");
+        for offset in (0..7).rev() {
+            text.delete_at(offset, offset + 1);
+        }
+        assert_eq!(text.text, "");
+        // backspacing over a word and the space after it goes together
+        assert_eq!(text.undo(), "foo");
+        assert_eq!(text.undo(), "foo bar");
+        assert_eq!(text.redo(), "foo");
+    }
+
+    #[test]
+    fn delete_forward() {
+        let mut text = Text::new("foo bar");
+        for _ in 0..7 {
+            text.delete_at(0, 0);
+        }
+        assert_eq!(text.text, "");
+        // going forward, the space goes with the word before it
+        assert_eq!(text.undo(), "bar");
+        assert_eq!(text.undo(), "foo bar");
+    }
+
+    #[test]
+    fn deletes_in_different_places() {
+        let mut text = Text::new("abcdef");
+        text.delete_at(5, 6);
+        text.delete_at(1, 2);
+        assert_eq!(text.text, "acde");
+        assert_eq!(text.undo(), "abcde");
+        assert_eq!(text.undo(), "abcdef");
+    }
+
+    /// A history with a branch in it, as states 0 to 3: "hello" typed, "!!" typed, both undone,
+    /// and "bye" typed instead. State `i` was made `10 * i` seconds in.
+    fn branched() -> Text {
+        let mut text = Text::new("");
+        text.type_at(0, "hello");
+        text.history.seal();
+        text.type_at(5, "!!");
+        text.undo();
+        text.undo();
+        text.type_at(0, "bye");
+        for (i, state) in text.history.states.iter_mut().enumerate() {
+            state.time = UNIX_EPOCH + Duration::from_secs(1000 + 10 * i as u64);
+        }
+        text
+    }
+
+    impl Text {
+        fn travel(&mut self, target: usize) -> &str {
+            apply(&mut self.text, self.history.travel(target));
+            &self.text
+        }
+
+        fn earlier(&mut self) -> &str {
+            apply(&mut self.text, self.history.earlier());
+            &self.text
+        }
+
+        fn later(&mut self) -> &str {
+            apply(&mut self.text, self.history.later());
+            &self.text
+        }
+
+        fn earlier_by(&mut self, secs: u64) -> &str {
+            apply(
+                &mut self.text,
+                self.history.earlier_by(Duration::from_secs(secs)),
+            );
+            &self.text
+        }
+
+        fn later_by(&mut self, secs: u64) -> &str {
+            apply(
+                &mut self.text,
+                self.history.later_by(Duration::from_secs(secs)),
+            );
+            &self.text
+        }
+    }
+
+    #[test]
+    fn travel() {
+        let mut text = branched();
+        assert_eq!(text.text, "bye");
+
+        // from one branch to the other: up to the root, and down the other side
+        let steps = text.history.travel(2);
+        match steps.as_slice() {
+            [Step::Revert(bye), Step::Apply(hello), Step::Apply(bangs)] => {
+                assert_eq!(bye[0].text, "bye");
+                assert_eq!(hello[0].text, "hello");
+                assert_eq!(bangs[0].text, "!!");
+            }
+            _ => panic!("expected to undo one step and redo two"),
+        }
+        apply(&mut text.text, steps);
+        assert_eq!(text.text, "hello!!");
+
+        // and to where it is already, which takes nothing
+        assert!(text.history.travel(2).is_empty());
+        assert_eq!(text.travel(1), "hello");
+        assert_eq!(text.travel(3), "bye");
+        assert_eq!(text.travel(0), "");
+        // redo follows the way that was last taken down from here
+        assert_eq!(text.redo(), "bye");
+        assert_eq!(text.travel(2), "hello!!");
+        assert_eq!(text.travel(0), "");
+        assert_eq!(text.redo(), "hello");
+        assert_eq!(text.redo(), "hello!!");
+    }
+
+    #[test]
+    fn earlier_and_later() {
+        let mut text = branched();
+        // in the order the states were made, whichever branch they're on
+        assert_eq!(text.earlier(), "hello!!");
+        assert_eq!(text.earlier(), "hello");
+        assert_eq!(text.earlier(), "");
+        assert_eq!(text.earlier(), "");
+        assert_eq!(text.later(), "hello");
+        assert_eq!(text.later(), "hello!!");
+        assert_eq!(text.later(), "bye");
+        assert_eq!(text.later(), "bye");
+        // undo still goes up the branch it's on
+        assert_eq!(text.undo(), "");
+    }
+
+    #[test]
+    fn earlier_and_later_by() {
+        let mut text = branched();
+        // 30 seconds in, less 5 seconds is 25 seconds in, when "!!" was the newest
+        assert_eq!(text.earlier_by(5), "hello!!");
+        assert_eq!(text.earlier_by(10), "hello");
+        assert_eq!(text.earlier_by(9), "");
+        // to the same state that's there already
+        assert_eq!(text.later_by(9), "");
+        assert_eq!(text.later_by(25), "hello!!");
+        assert_eq!(text.later_by(60 * 60), "bye");
+        // further back than the history goes, or forward, is as far as it goes
+        assert_eq!(text.earlier_by(60 * 60), "");
+        assert_eq!(text.later_by(u64::MAX), "bye");
+    }
+}
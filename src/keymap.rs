@@ -0,0 +1,490 @@
+//! Key bindings. Every mode has a table that maps sequences of keys to named commands, so a
+//! binding can be a single key like `ctrl-s` or a chord like `ctrl-x ctrl-s`. The defaults can be
+//! changed from the config file:
+//!
+//! ```text
+//! [keys.edit]
+//! ctrl-q = quit
+//! ctrl-x ctrl-w = save
+//! ctrl-s = none
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Which set of bindings is active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Editing text, which is where the editor starts.
+    Edit,
+    /// Answering a question in the minibuffer.
+    Prompt,
+}
+
+impl Mode {
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Edit => "edit",
+            Mode::Prompt => "prompt",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Mode> {
+        match name {
+            "edit" => Some(Mode::Edit),
+            "prompt" => Some(Mode::Prompt),
+            _ => None,
+        }
+    }
+}
+
+/// Defines `Command` along with the name each command goes by in the config file.
+macro_rules! commands {
+    ($($(#[$doc:meta])* $command:ident = $name:literal,)*) => {
+        /// Something the editor can do that a key can be bound to.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Command {
+            $($(#[$doc])* $command,)*
+        }
+
+        impl Command {
+            pub fn from_name(name: &str) -> Option<Command> {
+                match name {
+                    $($name => Some(Command::$command),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+commands! {
+    Quit = "quit",
+    /// Save the buffer, asking for a file name if it doesn't have one yet.
+    Save = "save",
+    /// Save the buffer under a new file name.
+    SaveAs = "save-as",
+    /// Answer the prompt with what's been typed.
+    Accept = "accept",
+    /// Close the prompt without answering it.
+    Cancel = "cancel",
+    /// Tab-complete what's typed into the prompt.
+    Complete = "complete",
+    /// Change the encoding the buffer gets saved in.
+    SetEncoding = "set-encoding",
+    /// Change the line endings of all lines when the buffer is next saved.
+    SetLineEnding = "set-line-ending",
+    /// Turn wrapping long lines on or off.
+    ToggleWrap = "toggle-wrap",
+    Undo = "undo",
+    Redo = "redo",
+    /// Go to the state of the text made right before the current one, on any undo branch.
+    Earlier = "earlier",
+    /// Go to the state of the text made right after the current one, on any undo branch.
+    Later = "later",
+    /// Go back to the text as it was some number of minutes ago, which is asked for.
+    EarlierBy = "earlier-by",
+    /// Go forward to the text as it was some number of minutes later.
+    LaterBy = "later-by",
+    InsertNewline = "insert-newline",
+    InsertTab = "insert-tab",
+    DeleteBackward = "delete-backward",
+    DeleteForward = "delete-forward",
+    /// Delete a single code point instead of a whole character, say to take an accent off.
+    DeleteCodepointBackward = "delete-codepoint-backward",
+    DeleteCodepointForward = "delete-codepoint-forward",
+    MoveLeft = "move-left",
+    MoveRight = "move-right",
+    MoveUp = "move-up",
+    MoveDown = "move-down",
+    MoveHome = "move-home",
+    MoveEnd = "move-end",
+    PageUp = "page-up",
+    PageDown = "page-down",
+}
+
+/// A key together with the modifiers held down with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // Shift is already part of the character (`A` rather than `a`) and of Shift-Tab, so
+        // leaving it in would make the same key press look different depending on the terminal
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers.difference(KeyModifiers::SHIFT),
+            _ => modifiers,
+        };
+
+        Self { code, modifiers }
+    }
+
+    /// The character this key types, if it's a key that types one.
+    pub fn text(&self) -> Option<char> {
+        match self.code {
+            KeyCode::Char(c) if self.modifiers.is_empty() => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Parses keys like `ctrl-x`, `alt-shift-left`, `pagedown` or `Q`.
+    pub fn parse(s: &str) -> Option<Key> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s;
+        // a lone `-` is the minus key, not the start of a modifier
+        while let Some((modifier, key)) = rest.split_once('-').filter(|(_, key)| !key.is_empty()) {
+            modifiers |= match modifier {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return None,
+            };
+            rest = key;
+        }
+
+        let code = match rest {
+            "enter" => KeyCode::Enter,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "esc" => KeyCode::Esc,
+            "space" => KeyCode::Char(' '),
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            _ => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                        KeyCode::Char(c.to_ascii_uppercase())
+                    }
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => {
+                        let n = rest.strip_prefix('f')?.parse().ok()?;
+                        KeyCode::F(n)
+                    }
+                }
+            }
+        };
+
+        Some(Key::new(code, modifiers))
+    }
+
+    /// Parses a space-separated sequence of keys, like `ctrl-x ctrl-s`.
+    pub fn parse_sequence(s: &str) -> Option<Vec<Key>> {
+        let keys: Option<Vec<Key>> = s.split_whitespace().map(Key::parse).collect();
+        keys.filter(|keys| !keys.is_empty())
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Key::new(event.code, event.modifiers)
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl-"),
+            (KeyModifiers::ALT, "alt-"),
+            (KeyModifiers::SHIFT, "shift-"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            KeyCode::BackTab => f.write_str("backtab"),
+            code => write!(f, "{}", format!("{code:?}").to_lowercase()),
+        }
+    }
+}
+
+enum Binding {
+    Command(Command),
+    /// The start of a chord; the next key decides.
+    Prefix(HashMap<Key, Binding>),
+}
+
+/// What a sequence of keys means.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lookup {
+    Command(Command),
+    /// The keys so far are the start of a chord.
+    Pending,
+    /// Nothing is bound to these keys.
+    Unbound,
+}
+
+pub struct Keymap {
+    tables: HashMap<Mode, HashMap<Key, Binding>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut keymap = Keymap {
+            tables: HashMap::new(),
+        };
+
+        for (keys, command) in [
+            ("ctrl-c", Command::Quit),
+            ("ctrl-x ctrl-c", Command::Quit),
+            ("ctrl-s", Command::Save),
+            ("ctrl-x ctrl-s", Command::Save),
+            ("ctrl-x ctrl-w", Command::SaveAs),
+            ("ctrl-x enter f", Command::SetEncoding),
+            ("ctrl-x enter l", Command::SetLineEnding),
+            ("ctrl-x w", Command::ToggleWrap),
+            ("ctrl-z", Command::Undo),
+            ("ctrl-y", Command::Redo),
+            ("alt-z", Command::Earlier),
+            ("alt-y", Command::Later),
+            ("ctrl-x [", Command::EarlierBy),
+            ("ctrl-x ]", Command::LaterBy),
+            ("enter", Command::InsertNewline),
+            ("shift-enter", Command::InsertNewline),
+            ("tab", Command::InsertTab),
+            ("backspace", Command::DeleteBackward),
+            ("delete", Command::DeleteForward),
+            ("ctrl-x backspace", Command::DeleteCodepointBackward),
+            ("ctrl-x delete", Command::DeleteCodepointForward),
+            ("left", Command::MoveLeft),
+            ("right", Command::MoveRight),
+            ("up", Command::MoveUp),
+            ("down", Command::MoveDown),
+            ("home", Command::MoveHome),
+            ("end", Command::MoveEnd),
+            ("pageup", Command::PageUp),
+            ("pagedown", Command::PageDown),
+        ] {
+            let keys = Key::parse_sequence(keys).expect("default key bindings parse");
+            keymap.bind(Mode::Edit, &keys, Some(command));
+        }
+
+        for (keys, command) in [
+            ("enter", Command::Accept),
+            ("esc", Command::Cancel),
+            ("ctrl-g", Command::Cancel),
+            ("ctrl-c", Command::Cancel),
+            ("tab", Command::Complete),
+            ("backspace", Command::DeleteBackward),
+            ("delete", Command::DeleteForward),
+            ("left", Command::MoveLeft),
+            ("right", Command::MoveRight),
+            ("home", Command::MoveHome),
+            ("end", Command::MoveEnd),
+        ] {
+            let keys = Key::parse_sequence(keys).expect("default key bindings parse");
+            keymap.bind(Mode::Prompt, &keys, Some(command));
+        }
+
+        keymap
+    }
+}
+
+impl Keymap {
+    /// Binds `keys` to `command` in `mode`, or unbinds them if `command` is `None`. Whatever was
+    /// bound to the keys before is replaced, including any longer chords that started with them.
+    pub fn bind(&mut self, mode: Mode, keys: &[Key], command: Option<Command>) {
+        let Some((last, prefix)) = keys.split_last() else {
+            return;
+        };
+
+        let mut table = self.tables.entry(mode).or_default();
+        for key in prefix {
+            let binding = table
+                .entry(*key)
+                .or_insert_with(|| Binding::Prefix(HashMap::new()));
+            if let Binding::Command(_) = binding {
+                *binding = Binding::Prefix(HashMap::new());
+            }
+            let Binding::Prefix(next) = binding else {
+                unreachable!();
+            };
+            table = next;
+        }
+
+        match command {
+            Some(command) => {
+                table.insert(*last, Binding::Command(command));
+            }
+            None => {
+                table.remove(last);
+            }
+        }
+    }
+
+    /// Looks up what `keys` (typed one after the other) do in `mode`.
+    pub fn lookup(&self, mode: Mode, keys: &[Key]) -> Lookup {
+        let Some(mut table) = self.tables.get(&mode) else {
+            return Lookup::Unbound;
+        };
+
+        for (i, key) in keys.iter().enumerate() {
+            match table.get(key) {
+                Some(Binding::Command(command)) if i == keys.len() - 1 => {
+                    return Lookup::Command(*command)
+                }
+                Some(Binding::Prefix(next)) => table = next,
+                _ => return Lookup::Unbound,
+            }
+        }
+
+        Lookup::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyEventKind;
+
+    use super::*;
+
+    fn key(s: &str) -> Key {
+        Key::parse(s).unwrap_or_else(|| panic!("{s} didn't parse"))
+    }
+
+    fn keys(s: &str) -> Vec<Key> {
+        Key::parse_sequence(s).unwrap_or_else(|| panic!("{s} didn't parse"))
+    }
+
+    /// What a terminal would send for `code` with `modifiers` held down.
+    fn pressed(code: KeyCode, modifiers: KeyModifiers) -> Key {
+        Key::from(KeyEvent::new_with_kind(
+            code,
+            modifiers,
+            KeyEventKind::Press,
+        ))
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(key("a"), pressed(KeyCode::Char('a'), KeyModifiers::NONE));
+        assert_eq!(
+            key("ctrl-x"),
+            pressed(KeyCode::Char('x'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            key("alt-shift-left"),
+            pressed(KeyCode::Left, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        );
+        assert_eq!(key("f5"), pressed(KeyCode::F(5), KeyModifiers::NONE));
+        assert_eq!(
+            key("space"),
+            pressed(KeyCode::Char(' '), KeyModifiers::NONE)
+        );
+
+        // minus is a key of its own, and can have modifiers too
+        assert_eq!(key("-"), pressed(KeyCode::Char('-'), KeyModifiers::NONE));
+        assert_eq!(
+            key("ctrl--"),
+            pressed(KeyCode::Char('-'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            key("ctrl-alt--"),
+            pressed(
+                KeyCode::Char('-'),
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            )
+        );
+
+        // shift is part of the character, however the terminal reports it
+        assert_eq!(key("shift-a"), key("A"));
+        assert_eq!(key("A"), pressed(KeyCode::Char('A'), KeyModifiers::SHIFT));
+        assert_eq!(key("shift-tab"), key("backtab"));
+        assert_eq!(
+            key("shift-tab"),
+            pressed(KeyCode::BackTab, KeyModifiers::SHIFT)
+        );
+        assert_eq!(
+            key("shift-tab"),
+            pressed(KeyCode::BackTab, KeyModifiers::NONE)
+        );
+        assert_ne!(key("shift-tab"), key("tab"));
+        assert_ne!(key("shift-left"), key("left"));
+
+        for bad in ["", "ctrl-", "hyper-x", "ctrl-xy", "fx", "-x"] {
+            assert_eq!(Key::parse(bad), None, "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn parse_sequence() {
+        assert_eq!(keys("ctrl-x  ctrl-s"), [key("ctrl-x"), key("ctrl-s")]);
+        assert_eq!(keys("ctrl-x -"), [key("ctrl-x"), key("-")]);
+        assert_eq!(Key::parse_sequence(""), None);
+        assert_eq!(Key::parse_sequence("ctrl-x hyper-s"), None);
+    }
+
+    #[test]
+    fn display_parses_back() {
+        for s in [
+            "a",
+            "A",
+            "-",
+            "ctrl--",
+            "backtab",
+            "alt-shift-left",
+            "f12",
+            "space",
+            "enter",
+        ] {
+            assert_eq!(key(&key(s).to_string()), key(s), "{s}");
+        }
+    }
+
+    #[test]
+    fn bind_and_lookup() {
+        let mut keymap = Keymap::default();
+        let lookup = |keymap: &Keymap, s: &str| keymap.lookup(Mode::Edit, &keys(s));
+        assert_eq!(lookup(&keymap, "ctrl-s"), Lookup::Command(Command::Save));
+        assert_eq!(lookup(&keymap, "ctrl-x"), Lookup::Pending);
+        assert_eq!(
+            lookup(&keymap, "ctrl-x ctrl-s"),
+            Lookup::Command(Command::Save)
+        );
+        assert_eq!(lookup(&keymap, "ctrl-x ctrl-q"), Lookup::Unbound);
+        // keys after a command don't go on to anything
+        assert_eq!(lookup(&keymap, "ctrl-s x"), Lookup::Unbound);
+        // and modes have bindings of their own
+        assert_eq!(
+            keymap.lookup(Mode::Prompt, &keys("ctrl-s")),
+            Lookup::Unbound
+        );
+
+        // a chord that starts with a key that was a command of its own takes its place
+        keymap.bind(Mode::Edit, &keys("ctrl-s x"), Some(Command::Quit));
+        assert_eq!(lookup(&keymap, "ctrl-s"), Lookup::Pending);
+        assert_eq!(lookup(&keymap, "ctrl-s x"), Lookup::Command(Command::Quit));
+
+        // and the other way round, along with all the chords that started with it
+        keymap.bind(Mode::Edit, &keys("ctrl-x"), Some(Command::Undo));
+        assert_eq!(lookup(&keymap, "ctrl-x"), Lookup::Command(Command::Undo));
+        assert_eq!(lookup(&keymap, "ctrl-x ctrl-s"), Lookup::Unbound);
+
+        // unbinding takes away just those keys
+        keymap.bind(Mode::Edit, &keys("ctrl-s x"), None);
+        assert_eq!(lookup(&keymap, "ctrl-s x"), Lookup::Unbound);
+        keymap.bind(Mode::Edit, &keys("ctrl-z"), None);
+        assert_eq!(lookup(&keymap, "ctrl-z"), Lookup::Unbound);
+        assert_eq!(lookup(&keymap, "ctrl-y"), Lookup::Command(Command::Redo));
+    }
+}
@@ -0,0 +1,347 @@
+//! Where each character of a line goes on screen. Most take up one cell, but tabs stretch to the
+//! next tab stop, East Asian wide characters and emoji take two, and control characters are shown
+//! as escapes like `^M`, because sent to the terminal as they are, they'd move its cursor around
+//! (or worse).
+//!
+//! Lines that are too long for the screen can be wrapped onto more than one row. They're broken
+//! after the last space that fits, or anywhere in a word that doesn't fit on a row by itself, and
+//! the rows after the first are indented with a marker so they don't look like lines of their own.
+
+use std::borrow::Cow;
+
+use crate::unicode;
+
+/// Shown before a combining mark that has nothing to combine with, so it doesn't get lost.
+const DOTTED_CIRCLE: &str = "\u{25cc}";
+
+/// What the rows a line gets wrapped onto start with, after the first.
+pub const WRAP_MARKER: &str = "\u{21aa}";
+/// How far those rows are indented, marker included.
+pub const WRAP_INDENT: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Text,
+    Tab,
+    /// A control character, shown as an escape.
+    Escape,
+}
+
+/// A grapheme cluster of a line, laid out on screen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glyph<'a> {
+    /// Byte offset of the cluster in the line.
+    pub offset: usize,
+    /// Length of the cluster in bytes.
+    pub len: usize,
+    /// The screen column it starts at.
+    pub col: usize,
+    /// How many cells it takes up.
+    pub width: usize,
+    /// What's drawn for it.
+    pub symbol: Cow<'a, str>,
+    pub kind: Kind,
+}
+
+impl Glyph<'_> {
+    fn is_space(&self) -> bool {
+        match self.kind {
+            Kind::Text => self.symbol.chars().all(char::is_whitespace),
+            Kind::Tab => true,
+            Kind::Escape => false,
+        }
+    }
+
+    /// What goes in cell `i` of the ones the glyph takes up. Text goes in the first cell and
+    /// leaves the others empty for the terminal to draw over, while tabs and escapes are drawn
+    /// cell by cell, so they can be cut off at the edge of the screen.
+    pub fn cell(&self, i: usize) -> &str {
+        match self.kind {
+            Kind::Text if i == 0 => &self.symbol,
+            Kind::Text => "",
+            Kind::Tab => " ",
+            // escapes are all ASCII
+            Kind::Escape => &self.symbol[i..=i],
+        }
+    }
+}
+
+/// Lays out `text`, a line without its line break, with tab stops every `tab_width` columns.
+pub fn glyphs(text: &str, tab_width: usize) -> Glyphs<'_> {
+    glyphs_at(text, tab_width, 0)
+}
+
+/// Lays out `text` like `glyphs` does, when it's the part of a line that starts at column `col`.
+/// It has to start and end on grapheme cluster boundaries of the whole line.
+pub fn glyphs_at(text: &str, tab_width: usize, col: usize) -> Glyphs<'_> {
+    Glyphs {
+        text,
+        tab_width,
+        offset: 0,
+        col,
+    }
+}
+
+pub struct Glyphs<'a> {
+    text: &'a str,
+    tab_width: usize,
+    offset: usize,
+    col: usize,
+}
+
+impl Glyphs<'_> {
+    /// The byte offset and screen column of the next glyph.
+    pub fn position(&self) -> (usize, usize) {
+        (self.offset, self.col)
+    }
+
+    /// Where the cluster at the current position ends and how wide it is, without working out
+    /// what's drawn for it. Getting to the part of a long line that's on screen takes measuring
+    /// everything before it, and most of that is usually plain ASCII, which is quick to measure.
+    fn measure(&self) -> Option<(usize, usize)> {
+        let bytes = self.text.as_bytes();
+        let &byte = bytes.get(self.offset)?;
+        // nothing ASCII combines with what's before it, so a printable ASCII character followed
+        // by more ASCII (or nothing) is a cluster of its own, one column wide
+        let next_ascii = bytes.get(self.offset + 1).is_none_or(u8::is_ascii);
+        if (0x20..0x7f).contains(&byte) && next_ascii {
+            return Some((self.offset + 1, 1));
+        }
+
+        let end = unicode::next_boundary(self.text, self.offset);
+        let cluster = &self.text[self.offset..end];
+        let first = cluster.chars().next()?;
+        let width = if first == '\t' {
+            self.tab_width - self.col % self.tab_width
+        } else if unicode::is_control(first) {
+            escape(first).len()
+        } else {
+            // with a dotted circle if it's just combining marks
+            unicode::width(cluster).max(1)
+        };
+
+        Some((end, width))
+    }
+
+    /// Skips over the glyphs that end at or before column `col`, so the next one is the one at
+    /// `col`, or the first after it if `col` is the second half of a wide glyph.
+    pub fn skip_to(&mut self, col: usize) {
+        while let Some((end, width)) = self.measure() {
+            if self.col + width > col {
+                break;
+            }
+            self.offset = end;
+            self.col += width;
+        }
+    }
+
+    /// Skips over the glyphs before byte `offset`.
+    pub fn skip_to_offset(&mut self, offset: usize) {
+        while self.offset < offset {
+            let Some((end, width)) = self.measure() else {
+                break;
+            };
+            self.offset = end;
+            self.col += width;
+        }
+    }
+}
+
+impl<'a> Iterator for Glyphs<'a> {
+    type Item = Glyph<'a>;
+
+    fn next(&mut self) -> Option<Glyph<'a>> {
+        let (end, width) = self.measure()?;
+        let cluster = &self.text[self.offset..end];
+        let first = cluster.chars().next()?;
+
+        let (symbol, kind) = if end - self.offset == 1 && (' '..='~').contains(&first) {
+            (Cow::Borrowed(cluster), Kind::Text)
+        } else if first == '\t' {
+            (Cow::Owned(" ".repeat(width)), Kind::Tab)
+        } else if unicode::is_control(first) {
+            (Cow::Owned(escape(first)), Kind::Escape)
+        } else if unicode::width(cluster) == 0 {
+            (Cow::Owned(format!("{DOTTED_CIRCLE}{cluster}")), Kind::Text)
+        } else {
+            (Cow::Borrowed(cluster), Kind::Text)
+        };
+
+        let glyph = Glyph {
+            offset: self.offset,
+            len: cluster.len(),
+            col: self.col,
+            width,
+            symbol,
+            kind,
+        };
+        self.offset = end;
+        self.col += width;
+
+        Some(glyph)
+    }
+}
+
+/// How a control character is shown: `^` and a letter for the ASCII ones, the way the terminal
+/// would echo them, and the code point for the rest.
+fn escape(c: char) -> String {
+    match c {
+        '\0'..='\x1f' => format!("^{}", char::from(c as u8 + 0x40)),
+        '\x7f' => "^?".to_string(),
+        _ => format!("<U+{:04X}>", u32::from(c)),
+    }
+}
+
+/// The screen column that byte `offset` of `text` is at.
+pub fn col_of_offset(text: &str, offset: usize, tab_width: usize) -> usize {
+    let mut glyphs = glyphs(text, tab_width);
+    glyphs.skip_to_offset(offset);
+    glyphs.col
+}
+
+/// The byte offset of what's at screen column `col` of `text`, or of the end of the line if it's
+/// shorter than that.
+pub fn offset_of_col(text: &str, col: usize, tab_width: usize) -> usize {
+    let mut glyphs = glyphs(text, tab_width);
+    glyphs.skip_to(col);
+    glyphs.offset
+}
+
+/// How many columns `text` takes up.
+pub fn width(text: &str, tab_width: usize) -> usize {
+    let mut glyphs = glyphs(text, tab_width);
+    glyphs.skip_to(usize::MAX);
+    glyphs.col
+}
+
+/// One row of the screen that a wrapped line takes up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Row {
+    /// Byte offset in the line where the row starts.
+    pub start: usize,
+    /// Byte offset in the line where the row ends, which is where the next one starts.
+    pub end: usize,
+    /// The column the row starts at, as the line would be laid out without wrapping.
+    pub col: usize,
+}
+
+impl Row {
+    /// How far the row is indented on screen.
+    pub fn indent(&self) -> usize {
+        if self.start == 0 {
+            0
+        } else {
+            WRAP_INDENT
+        }
+    }
+
+    /// How many columns of the line fit on the row, if the screen is `width` wide.
+    fn room(&self, width: usize) -> usize {
+        width.saturating_sub(self.indent()).max(1)
+    }
+}
+
+/// Wraps `text`, a line without its line break, onto rows `screen_width` columns wide. A line always
+/// takes up at least one row, and a line that fills its last row exactly gets an empty one after
+/// it, where the cursor can go to get to the end of the line.
+pub fn wrap(text: &str, tab_width: usize, screen_width: usize) -> Vec<Row> {
+    let mut rows = Vec::new();
+    let mut wrapper = Wrapper::new(screen_width, 0, 0);
+    for glyph in glyphs(text, tab_width) {
+        wrapper.push(0, &glyph, &mut rows);
+    }
+    wrapper.finish(text.len(), &mut rows);
+
+    rows
+}
+
+/// Wraps a line a glyph at a time, which is what `wrap` does with all of a line at once. It can
+/// also pick up partway through a line, at the start of any row: where the rows after that break
+/// only depends on where that row starts and the column it's at.
+pub struct Wrapper {
+    screen_width: usize,
+    /// The row the glyphs are going on.
+    row: Row,
+    /// The offset and column after the last space on the row, where it would be best broken.
+    word: Option<(usize, usize)>,
+    after_space: bool,
+    /// The column after the last glyph.
+    end: usize,
+}
+
+impl Wrapper {
+    /// Starts wrapping onto rows `screen_width` columns wide, from a row that starts at byte
+    /// `start` of the line and at column `col`.
+    pub fn new(screen_width: usize, start: usize, col: usize) -> Self {
+        Self {
+            screen_width,
+            row: Row {
+                start,
+                end: start,
+                col,
+            },
+            word: None,
+            after_space: false,
+            end: col,
+        }
+    }
+
+    /// The row the next glyph goes on, as far as it's known.
+    pub fn row(&self) -> Row {
+        self.row
+    }
+
+    /// Puts the next glyph of the line on a row, and adds the rows that finishes to `rows`. The
+    /// glyph is from the part of the line that starts at byte `base`.
+    pub fn push(&mut self, base: usize, glyph: &Glyph, rows: &mut Vec<Row>) {
+        let offset = base + glyph.offset;
+        // the glyph doesn't fit, unless it's the only thing on the row, in which case it has to
+        // fit anyway. Breaking after a space can move more than just the glyph to the next row,
+        // which is indented, so it may not fit there either.
+        while glyph.col + glyph.width - self.row.col > self.row.room(self.screen_width)
+            && offset > self.row.start
+        {
+            let (start, col) = self
+                .word
+                .filter(|&(start, _)| start > self.row.start)
+                .unwrap_or((offset, glyph.col));
+            rows.push(Row {
+                end: start,
+                ..self.row
+            });
+            self.row = Row {
+                start,
+                end: start,
+                col,
+            };
+            self.word = None;
+        }
+
+        let space = glyph.is_space();
+        if self.after_space && !space {
+            self.word = Some((offset, glyph.col));
+        }
+        self.after_space = space;
+        self.end = glyph.col + glyph.width;
+    }
+
+    /// Finishes the last row at byte `len`, the end of the line.
+    pub fn finish(self, len: usize, rows: &mut Vec<Row>) {
+        let row = self.row;
+        rows.push(Row { end: len, ..row });
+        if self.end - row.col >= row.room(self.screen_width) && len > 0 {
+            rows.push(Row {
+                start: len,
+                end: len,
+                col: self.end,
+            });
+        }
+    }
+}
+
+/// Which of `rows` the byte at `offset` is on.
+pub fn row_of_offset(rows: &[Row], offset: usize) -> usize {
+    rows.iter()
+        .rposition(|row| row.start <= offset)
+        .unwrap_or(0)
+}
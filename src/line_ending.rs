@@ -0,0 +1,108 @@
+//! Line endings. Files from Windows end their lines with `\r\n` instead of `\n`, and the editor
+//! keeps to whichever one a file uses, so editing a line doesn't turn up in diffs as a change to
+//! every line.
+
+use std::fmt;
+use std::ops::{AddAssign, SubAssign};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    pub const ALL: [LineEnding; 2] = [LineEnding::Lf, LineEnding::Crlf];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "lf",
+            LineEnding::Crlf => "crlf",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<LineEnding> {
+        let name = name.trim().to_ascii_lowercase();
+        LineEnding::ALL
+            .into_iter()
+            .find(|ending| ending.name() == name)
+    }
+
+    /// `text` with all its line endings changed to this one.
+    pub fn normalize(self, text: &str) -> String {
+        let lf = text.replace("\r\n", "\n");
+        match self {
+            LineEnding::Lf => lf,
+            LineEnding::Crlf => lf.replace('\n', "\r\n"),
+        }
+    }
+}
+
+impl fmt::Display for LineEnding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How many lines end in each kind of line ending. Counts can be added and taken away, so the
+/// ones for a whole buffer can be kept up to date by counting just what an edit touches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub lf: usize,
+    pub crlf: usize,
+}
+
+impl Counts {
+    pub fn of(text: &str) -> Counts {
+        let newlines = text.matches('\n').count();
+        let crlf = text.matches("\r\n").count();
+
+        Counts {
+            lf: newlines - crlf,
+            crlf,
+        }
+    }
+
+    /// The line ending that's used the most. Text without any line breaks gets `\n`.
+    pub fn most_used(self) -> LineEnding {
+        if self.crlf > self.lf {
+            LineEnding::Crlf
+        } else {
+            LineEnding::Lf
+        }
+    }
+
+    /// Whether both kinds are used.
+    pub fn is_mixed(self) -> bool {
+        self.lf > 0 && self.crlf > 0
+    }
+
+    /// How many lines end in something other than `ending`.
+    pub fn other_than(self, ending: LineEnding) -> usize {
+        match ending {
+            LineEnding::Lf => self.crlf,
+            LineEnding::Crlf => self.lf,
+        }
+    }
+}
+
+impl AddAssign for Counts {
+    fn add_assign(&mut self, other: Counts) {
+        self.lf += other.lf;
+        self.crlf += other.crlf;
+    }
+}
+
+impl SubAssign for Counts {
+    fn sub_assign(&mut self, other: Counts) {
+        self.lf -= other.lf;
+        self.crlf -= other.crlf;
+    }
+}
@@ -1,15 +1,65 @@
-use std::fs::File;
-use std::io::{BufWriter, Stdout, Write};
-use std::path::PathBuf;
+mod columns;
+mod config;
+mod diff;
+mod dirs;
+mod encoding;
+mod gutter;
+mod history;
+mod keymap;
+mod layout;
+mod line_ending;
+mod prompt;
+mod recovery;
+mod render;
+mod rope;
+mod save;
+mod swap;
+#[cfg(test)]
+mod testing;
+mod undofile;
+mod unicode;
+mod view;
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, Stdout, Write};
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use clap::Parser;
-use crossterm::{cursor, queue};
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    event::{self, Event, KeyEvent, KeyEventKind},
     execute,
-    style::Print,
+    style::{ContentStyle, Print, Stylize},
     terminal,
 };
+use signal_hook::{
+    consts::{SIGHUP, SIGTERM},
+    flag,
+};
+
+use columns::Columns;
+use config::Config;
+use encoding::Encoding;
+use gutter::{Gutter, Sign};
+use history::{Edit, EditKind, History, Step};
+use keymap::{Command, Key, Lookup, Mode};
+use layout::Glyph;
+use line_ending::LineEnding;
+use prompt::{expand_path, Prompt, Purpose};
+use render::{Frame, Renderer};
+use rope::Rope;
+use swap::Swap;
+use undofile::Loaded;
+use view::Viewport;
 
 #[derive(Debug)]
 enum BufferPath {
@@ -17,158 +67,1866 @@ enum BufferPath {
     Temp(usize),
 }
 
+impl BufferPath {
+    /// A name for keeping things about the buffer under in the state directory: the file's full
+    /// path with its `/`s escaped.
+    fn state_name(&self) -> String {
+        match self {
+            BufferPath::File(path) => {
+                let path = std::path::absolute(path).unwrap_or_else(|_| path.clone());
+                dirs::escape_path(&path)
+            }
+            BufferPath::Temp(n) => format!("scratch-{n}"),
+        }
+    }
+
+    /// Where the swap file for the buffer goes. Scratch buffers get the process id in there too,
+    /// since scratch numbers only mean something inside one process.
+    fn swap_path(&self) -> Option<PathBuf> {
+        match self {
+            BufferPath::File(_) => swap::path(&self.state_name()),
+            BufferPath::Temp(_) => swap::path(&format!("{}.{}", self.state_name(), process::id())),
+        }
+    }
+}
+
+impl fmt::Display for BufferPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BufferPath::File(path) => write!(f, "{}", path.display()),
+            BufferPath::Temp(n) => write!(f, "[scratch {n}]"),
+        }
+    }
+}
+
 struct Buffer {
     path: BufferPath,
-    data: String,
+    text: Rope,
+    history: History,
+    /// Hash of the text as it was last loaded from or saved to disk.
+    saved_hash: u64,
+    /// Goes up with every change to the text, to tell whether it changed since some point.
+    revision: u64,
+    /// Where the swap file goes, or `None` if the buffer doesn't get one.
+    swap_path: Option<PathBuf>,
+    /// The revision that's in the swap file, if there is one.
+    swapped: Option<u64>,
+    swap_writer: swap::Writer,
+    /// Set when saving over the file wouldn't work or would damage it. Saving under a different
+    /// name is still fine.
+    read_only: bool,
+    /// What the file is encoded in, and so what it's saved in.
+    encoding: Encoding,
+    /// The encoding the file on disk is in.
+    saved_encoding: Encoding,
+    /// What new lines get ended with.
+    line_ending: LineEnding,
+    /// How many lines end in `\n` and how many in `\r\n`, kept up to date with every change.
+    endings: line_ending::Counts,
+    /// Set when all line endings are to be changed to `line_ending` the next time the buffer is
+    /// saved.
+    normalize_endings: bool,
+    /// The screen columns of places in long lines, so drawing doesn't need to lay them out from
+    /// the start. Filled in as they're drawn, and forgotten where they're edited.
+    columns: RefCell<Columns>,
 }
 impl Buffer {
     fn new(path: BufferPath, data: String) -> Self {
-        Self { path, data }
+        let swap_path = path.swap_path();
+        let endings = line_ending::Counts::of(&data);
+
+        Self {
+            path,
+            saved_hash: undofile::hash(std::iter::once(data.as_str())),
+            text: Rope::new(&data),
+            history: History::default(),
+            revision: 0,
+            swap_path,
+            swapped: None,
+            swap_writer: swap::Writer::new(),
+            read_only: false,
+            encoding: Encoding::Utf8,
+            saved_encoding: Encoding::Utf8,
+            line_ending: endings.most_used(),
+            endings,
+            normalize_endings: false,
+            columns: RefCell::default(),
+        }
+    }
+
+    /// Brings back the undo history from the last time this file was edited, if the file hasn't
+    /// changed since. Returns whether there was one to bring back.
+    fn load_history(&mut self) -> io::Result<bool> {
+        let BufferPath::File(ref path) = self.path else {
+            return Ok(false);
+        };
+
+        match undofile::load(path, self.saved_hash)? {
+            Loaded::Restored(history) => {
+                self.history = history;
+                Ok(true)
+            }
+            Loaded::Missing | Loaded::Stale => Ok(false),
+        }
+    }
+
+    /// Keeps the undo history around for the next time this file is opened.
+    fn save_history(&self) -> io::Result<()> {
+        match self.path {
+            BufferPath::File(ref path) => undofile::save(path, &self.history, self.saved_hash),
+            BufferPath::Temp(_) => Ok(()),
+        }
+    }
+
+    /// Whether the text has changed since it was last loaded or saved. Undoing back to that point
+    /// counts as unchanged.
+    fn is_modified(&self) -> bool {
+        !self.history.is_saved() || self.encoding != self.saved_encoding || self.normalize_endings
+    }
+
+    /// Called once the text has been written to disk.
+    fn mark_saved(&mut self) {
+        self.history.mark_saved();
+        self.saved_hash = undofile::hash(self.text.chunks());
+        self.saved_encoding = self.encoding;
     }
 
-    fn append_char(&mut self, c: char) {
-        self.data.push(c);
+    /// The text the way it goes into the file, in the buffer's encoding.
+    fn encode(&self) -> io::Result<Vec<u8>> {
+        let mut bytes = self.encoding.bom().to_vec();
+        let mut offset = 0;
+        for chunk in self.text.chunks() {
+            if let Err(c) = self.encoding.encode(chunk, &mut bytes) {
+                // everything before it was fine, so this is the first one
+                let line = self.line_of_offset(offset + chunk.find(c).unwrap_or(0)) + 1;
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{c:?} on line {line} can't be written in {}", self.encoding),
+                ));
+            }
+            offset += chunk.len();
+        }
+
+        Ok(bytes)
+    }
+
+    /// Switches to saving the text in `encoding` from now on. Fails if there's something in the
+    /// text that `encoding` can't represent.
+    fn set_encoding(&mut self, encoding: Encoding) -> Result<(), char> {
+        let mut scratch = Vec::new();
+        for chunk in self.text.chunks() {
+            encoding.encode(chunk, &mut scratch)?;
+            scratch.clear();
+        }
+        self.encoding = encoding;
+
+        Ok(())
     }
 
-    fn delete_char_from_end(&mut self) {
-        if !self.data.is_empty() {
-            self.data.pop();
+    /// Brings the swap file up to date: writes the text to it if there are unsaved changes that
+    /// aren't in it yet, and removes it once there are none. That happens in the background, so
+    /// an error is from an earlier update.
+    fn update_swap(&mut self) -> io::Result<()> {
+        if let Some((_, e)) = self.swap_writer.failure() {
+            return Err(e);
+        }
+        let Some(ref path) = self.swap_path else {
+            return Ok(());
+        };
+
+        if !self.is_modified() {
+            if self.swapped.is_some() {
+                self.remove_swap();
+            }
+            return Ok(());
+        }
+        if self.swapped != Some(self.revision) {
+            self.swap_writer
+                .write(path.clone(), self.text.chunks().collect());
+            self.swapped = Some(self.revision);
         }
+
+        Ok(())
+    }
+
+    /// Removes the swap file, including one that was left behind by an earlier editor.
+    fn remove_swap(&mut self) {
+        if let Some(ref path) = self.swap_path {
+            self.swap_writer.remove(path.clone());
+        }
+        self.swapped = None;
+    }
+
+    /// Waits for the swap file to be written or removed, for when the editor is about to exit.
+    fn flush_swap(&self) -> io::Result<()> {
+        self.swap_writer.flush()
+    }
+
+    /// Moves the swap file to where it goes for the buffer's path, right after the buffer was
+    /// saved under a new one. The old one is removed, or it would be offered as changes to the old
+    /// file after a crash.
+    fn move_swap(&mut self) {
+        self.remove_swap();
+        self.swap_path = self.path.swap_path().filter(|path| !swap::in_use(path));
+    }
+
+    /// Changes all line endings to `line_ending`, as a single step that can be undone.
+    fn normalize_line_endings(&mut self) {
+        let text: String = self.text.chunks().collect();
+        let normalized = self.line_ending.normalize(&text);
+        if normalized != text {
+            self.replace_text(&normalized);
+        }
+        self.normalize_endings = false;
+    }
+
+    /// Replaces the text with `text`, as a single step that can be undone.
+    fn replace_text(&mut self, text: &str) {
+        let current: String = self.text.chunks().collect();
+
+        // only the part that's different gets replaced, which keeps the undo step small
+        let prefix = current
+            .char_indices()
+            .zip(text.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(current.len().min(text.len()), |((i, _), _)| i);
+        let suffix = current[prefix..]
+            .chars()
+            .rev()
+            .zip(text[prefix..].chars().rev())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum::<usize>();
+        let removed = &current[prefix..current.len() - suffix];
+        let inserted = &text[prefix..text.len() - suffix];
+
+        let columns = self.columns.get_mut();
+        remove_text(
+            &mut self.text,
+            &mut self.endings,
+            columns,
+            prefix..prefix + removed.len(),
+        );
+        insert_text(&mut self.text, &mut self.endings, columns, prefix, inserted);
+        self.revision += 1;
+        self.history.record_group(vec![
+            Edit {
+                kind: EditKind::Delete,
+                offset: prefix,
+                text: removed.to_string(),
+                cursor: prefix,
+            },
+            Edit {
+                kind: EditKind::Insert,
+                offset: prefix,
+                text: inserted.to_string(),
+                cursor: prefix,
+            },
+        ]);
     }
+
+    /// Number of lines in the buffer. A trailing newline starts a new (empty) line, so an empty
+    /// buffer still has one line.
+    fn line_count(&self) -> usize {
+        self.text.line_count()
+    }
+
+    /// Byte offset at which `line` starts.
+    fn line_start(&self, line: usize) -> usize {
+        self.text.line_to_byte(line)
+    }
+
+    /// Length in bytes of `line`, not counting its line ending.
+    fn line_len(&self, line: usize) -> usize {
+        let end = self.text.line_to_byte(line + 1);
+        let newline = self
+            .ending_of(line)
+            .map_or(0, |ending| ending.as_str().len());
+
+        end - self.line_start(line) - newline
+    }
+
+    /// How `line` ends, which is with nothing if it's the last one.
+    fn ending_of(&self, line: usize) -> Option<LineEnding> {
+        if line + 1 >= self.line_count() {
+            return None;
+        }
+
+        let end = self.text.line_to_byte(line + 1);
+        match self.char_before(end - 1) {
+            Some('\r') => Some(LineEnding::Crlf),
+            _ => Some(LineEnding::Lf),
+        }
+    }
+
+    /// The byte range of `line` in the text, without its newline.
+    fn line_range(&self, line: usize) -> Range<usize> {
+        let start = self.line_start(line);
+        start..start + self.line_len(line)
+    }
+
+    /// The screen column that byte `col` of `line` is at.
+    fn col_of_offset(&self, line: usize, col: usize, tab_width: usize) -> usize {
+        self.columns
+            .borrow_mut()
+            .col_of_offset(&self.text, self.line_range(line), col, tab_width)
+    }
+
+    /// The part of `line` that's on screen columns `cols`, give or take a bit on either side: its
+    /// text, the byte offset in the line it starts at, and the column it starts at. Unlike the
+    /// whole line, it's never much longer than what fits on screen.
+    fn section(
+        &self,
+        line: usize,
+        cols: Range<usize>,
+        tab_width: usize,
+    ) -> (Cow<'_, str>, usize, usize) {
+        let range = self.line_range(line);
+        let start = range.start;
+        let (section, col) = self
+            .columns
+            .borrow_mut()
+            .section(&self.text, range, cols, tab_width);
+
+        (
+            self.text.slice(start + section.start..start + section.end),
+            section.start,
+            col,
+        )
+    }
+
+    /// The rows `line` is wrapped onto, on a screen `width` columns wide.
+    fn rows(&self, line: usize, tab_width: usize, width: usize) -> Rc<Vec<layout::Row>> {
+        self.columns
+            .borrow_mut()
+            .rows(&self.text, self.line_range(line), tab_width, width)
+    }
+
+    /// The byte offset in `line` of what's at screen column `col`, or of the end of the line if
+    /// it's shorter than that.
+    fn offset_of_col(&self, line: usize, col: usize, tab_width: usize) -> usize {
+        let (text, offset, start) = self.section(line, col..col, tab_width);
+        let mut glyphs = layout::glyphs_at(&text, tab_width, start);
+        glyphs.skip_to(col);
+
+        offset + glyphs.position().0
+    }
+
+    /// The line that the byte at `offset` belongs to.
+    fn line_of_offset(&self, offset: usize) -> usize {
+        self.text.byte_to_line(offset)
+    }
+
+    /// The character that starts at `offset`, if any.
+    fn char_at(&self, offset: usize) -> Option<char> {
+        self.text.char_at(offset)
+    }
+
+    /// The character that ends at `offset`, if any.
+    fn char_before(&self, offset: usize) -> Option<char> {
+        self.text.char_before(offset)
+    }
+
+    /// Length in bytes of the grapheme cluster that starts at `offset`, if any. Only the text up
+    /// to the first place after it that's a boundary whatever came before (like the next line
+    /// break, or between two plain ASCII characters) needs looking at, which is rarely more than
+    /// a few characters away.
+    fn next_grapheme_len(&self, offset: usize) -> Option<usize> {
+        let mut prev = self.char_at(offset)?;
+        let mut end = offset + prev.len_utf8();
+        while let Some(c) = self.char_at(end) {
+            if unicode::is_boundary(prev, c) {
+                break;
+            }
+            end += c.len_utf8();
+            prev = c;
+        }
+
+        let text = self.text.slice(offset..end);
+        Some(unicode::next_boundary(&text, 0))
+    }
+
+    /// Length in bytes of the grapheme cluster that ends at `offset`, if any. Finding where it
+    /// starts means working forward from a boundary before it, so this goes back to the nearest
+    /// one that's a boundary whatever came before.
+    fn prev_grapheme_len(&self, offset: usize) -> Option<usize> {
+        let mut next = self.char_before(offset)?;
+        let mut start = offset - next.len_utf8();
+        while let Some(c) = self.char_before(start) {
+            if unicode::is_boundary(c, next) {
+                break;
+            }
+            start -= c.len_utf8();
+            next = c;
+        }
+
+        let text = self.text.slice(start..offset);
+        Some(offset - start - unicode::prev_boundary(&text, offset - start))
+    }
+
+    /// Inserts `text` at `offset`. `cursor` is where the cursor was before the edit, so undo can
+    /// put it back there.
+    fn insert(&mut self, offset: usize, text: &str, cursor: usize) {
+        let columns = self.columns.get_mut();
+        insert_text(&mut self.text, &mut self.endings, columns, offset, text);
+        self.revision += 1;
+        self.history.record(Edit {
+            kind: EditKind::Insert,
+            offset,
+            text: text.to_string(),
+            cursor,
+        });
+    }
+
+    /// Deletes the text in `range` and returns it. `cursor` is where the cursor was before the
+    /// edit, so undo can put it back there.
+    fn delete(&mut self, range: Range<usize>, cursor: usize) -> String {
+        let text = self.text.slice(range.clone()).into_owned();
+        let columns = self.columns.get_mut();
+        remove_text(&mut self.text, &mut self.endings, columns, range.clone());
+        self.revision += 1;
+        self.history.record(Edit {
+            kind: EditKind::Delete,
+            offset: range.start,
+            text: text.clone(),
+            cursor,
+        });
+
+        text
+    }
+
+    /// Takes the text wherever `walk` goes in the undo tree. Going nowhere, as when there's nothing
+    /// left to undo, doesn't count as a change. Returns where the cursor should go.
+    fn walk_history(&mut self, walk: impl FnOnce(&mut History) -> Vec<Step<'_>>) -> Option<usize> {
+        let steps = walk(&mut self.history);
+        if !steps.is_empty() {
+            self.revision += 1;
+        }
+        apply_steps(
+            &mut self.text,
+            &mut self.endings,
+            self.columns.get_mut(),
+            steps,
+        )
+    }
+
+    /// Reverts the last undo step. Returns where the cursor should go.
+    fn undo(&mut self) -> Option<usize> {
+        self.walk_history(History::undo)
+    }
+
+    /// Re-applies the last undone step. Returns where the cursor should go.
+    fn redo(&mut self) -> Option<usize> {
+        self.walk_history(History::redo)
+    }
+
+    /// Goes to the previous state of the text in the order they were made, jumping across
+    /// branches of the undo tree. Returns where the cursor should go.
+    fn earlier(&mut self) -> Option<usize> {
+        self.walk_history(History::earlier)
+    }
+
+    /// Goes to the next state of the text in the order they were made, jumping across branches
+    /// of the undo tree. Returns where the cursor should go.
+    fn later(&mut self) -> Option<usize> {
+        self.walk_history(History::later)
+    }
+
+    /// Restores the text as it was `ago` before its current state. Returns where the cursor
+    /// should go.
+    fn earlier_by(&mut self, ago: Duration) -> Option<usize> {
+        self.walk_history(|history| history.earlier_by(ago))
+    }
+
+    /// The opposite of `earlier_by`. Returns where the cursor should go.
+    fn later_by(&mut self, later: Duration) -> Option<usize> {
+        self.walk_history(|history| history.later_by(later))
+    }
+}
+
+/// Walks `text` through the undo tree. Returns where the cursor should end up: where it was
+/// before the last reverted edit, or right after the last re-applied one.
+fn apply_steps(
+    text: &mut Rope,
+    endings: &mut line_ending::Counts,
+    columns: &mut Columns,
+    steps: Vec<Step>,
+) -> Option<usize> {
+    let mut cursor = None;
+    for step in steps {
+        match step {
+            Step::Revert(edits) => {
+                for edit in edits.iter().rev() {
+                    match edit.kind {
+                        EditKind::Insert => remove_text(
+                            text,
+                            endings,
+                            columns,
+                            edit.offset..edit.offset + edit.text.len(),
+                        ),
+                        EditKind::Delete => {
+                            insert_text(text, endings, columns, edit.offset, &edit.text)
+                        }
+                    }
+                }
+                cursor = edits.first().map(|edit| edit.cursor);
+            }
+            Step::Apply(edits) => {
+                for edit in edits {
+                    match edit.kind {
+                        EditKind::Insert => {
+                            insert_text(text, endings, columns, edit.offset, &edit.text)
+                        }
+                        EditKind::Delete => remove_text(
+                            text,
+                            endings,
+                            columns,
+                            edit.offset..edit.offset + edit.text.len(),
+                        ),
+                    }
+                }
+                cursor = edits.last().map(Edit::cursor_after);
+            }
+        }
+    }
+
+    cursor
+}
+
+/// Inserts `text` into `rope` at `offset`, keeping the count of line endings in `endings` and the
+/// known `columns` up to date.
+fn insert_text(
+    rope: &mut Rope,
+    endings: &mut line_ending::Counts,
+    columns: &mut Columns,
+    offset: usize,
+    text: &str,
+) {
+    forget_columns(rope, columns, offset..offset, text);
+    *endings -= endings_around(rope, offset..offset);
+    rope.insert(offset, text);
+    *endings += endings_around(rope, offset..offset + text.len());
+}
+
+/// Removes `range` from `rope`, keeping the count of line endings in `endings` and the known
+/// `columns` up to date.
+fn remove_text(
+    rope: &mut Rope,
+    endings: &mut line_ending::Counts,
+    columns: &mut Columns,
+    range: Range<usize>,
+) {
+    forget_columns(rope, columns, range.clone(), "");
+    *endings -= endings_around(rope, range.clone());
+    rope.remove(range.clone());
+    *endings += endings_around(rope, range.start..range.start);
+}
+
+/// Forgets the columns and rows that replacing `range` of `rope` with `text` changes: those of the
+/// line it's on, from about there on, or everything if lines are added or taken away.
+fn forget_columns(rope: &Rope, columns: &mut Columns, range: Range<usize>, text: &str) {
+    let line = rope.byte_to_line(range.start);
+    if text.contains('\n') || rope.byte_to_line(range.end) != line {
+        columns.clear();
+    } else {
+        let start = rope.line_to_byte(line);
+        columns.edited(line, range.start - start..range.end - start, text.len());
+    }
+}
+
+/// Counts the line endings in `range` of `rope`, along with a `\r\n` that's half in it. An edit
+/// of the range can split one of those or make a new one, by putting something between the `\r`
+/// and the `\n` or taking away what was there.
+fn endings_around(rope: &Rope, range: Range<usize>) -> line_ending::Counts {
+    let start = match rope.char_before(range.start) {
+        Some('\r') => range.start - 1,
+        _ => range.start,
+    };
+    let end = match rope.char_at(range.end) {
+        Some('\n') => range.end + 1,
+        _ => range.end,
+    };
+
+    line_ending::Counts::of(&rope.slice(start..end))
+}
+
+/// Where the user is in the buffer. `offset` is the source of truth; `line` and `col` are kept in
+/// sync with it so that drawing doesn't need to search the buffer.
+#[derive(Debug, Default, Clone, Copy)]
+struct Cursor {
+    line: usize,
+    /// Byte offset from the start of `line`.
+    col: usize,
+    /// Byte offset from the start of the buffer.
+    offset: usize,
+    /// The screen column the cursor tries to get back to when moving up and down, so that
+    /// passing over a short line doesn't lose the position on longer ones. When lines are
+    /// wrapped, it's the column from the start of the row.
+    goal_col: usize,
 }
 
 struct Editor {
     buffer: Buffer,
+    cursor: Cursor,
+    config: Config,
+    /// How wide the rows are that lines are wrapped onto, if they're wrapped.
+    wrap_width: Option<usize>,
 }
 impl Editor {
-    fn new(buffer: Buffer) -> Editor {
-        Editor { buffer }
+    fn new(buffer: Buffer, config: Config) -> Editor {
+        Editor {
+            buffer,
+            cursor: Cursor::default(),
+            config,
+            wrap_width: None,
+        }
     }
 
-    fn save_to_disk(&self) -> std::io::Result<()> {
+    fn save_to_disk(&mut self) -> io::Result<()> {
+        if self.buffer.normalize_endings {
+            // the lines stay the same, but where they start doesn't
+            let Cursor { line, col, .. } = self.cursor;
+            self.buffer.normalize_line_endings();
+            self.set_offset(self.buffer.line_start(line) + col);
+        }
+
         if let BufferPath::File(ref file_path) = self.buffer.path {
-            let mut f = BufWriter::new(File::create(file_path)?);
-            f.write(self.buffer.data.as_bytes())?;
+            save::write_file(file_path, &self.buffer.encode()?)?;
+            self.buffer.mark_saved();
         }
 
         Ok(())
     }
 
+    /// Saves the buffer to `path`, which is where it gets saved from then on. If saving fails
+    /// the buffer stays with the path it had.
+    fn save_as(&mut self, path: PathBuf) -> io::Result<()> {
+        let old = std::mem::replace(&mut self.buffer.path, BufferPath::File(path));
+        let result = self.save_to_disk();
+        match result {
+            Ok(()) => {
+                self.buffer.read_only = false;
+                self.buffer.move_swap();
+            }
+            Err(_) => self.buffer.path = old,
+        }
+
+        result
+    }
+
     fn insert_char(&mut self, c: char) {
-        self.buffer.append_char(c);
+        self.insert_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    fn insert_str(&mut self, text: &str) {
+        let offset = self.cursor.offset;
+        self.buffer.insert(offset, text, offset);
+        self.set_offset(offset + text.len());
+    }
+
+    fn insert_newline(&mut self) {
+        self.insert_str(self.buffer.line_ending.as_str());
+    }
+
+    /// Inserts a tab, or as many spaces as it takes to get to the next tab stop if tabs are
+    /// expanded.
+    fn insert_tab(&mut self) {
+        if self.config.expand_tabs {
+            let col = self.cursor_col();
+            let width = self.config.tab_width - col % self.config.tab_width;
+            self.insert_str(&" ".repeat(width));
+        } else {
+            self.insert_char('\t');
+        }
     }
 
+    /// Backspace: deletes the character before the cursor, all of it even if it's made of more
+    /// than one code point.
     fn delete_last_char(&mut self) {
-        self.buffer.delete_char_from_end();
+        let end = self.cursor.offset;
+        if let Some(len) = self.buffer.prev_grapheme_len(end) {
+            let start = end - len;
+            self.buffer.delete(start..end, end);
+            self.set_offset(start);
+        }
+    }
+
+    /// Delete: deletes the character under the cursor, all of it.
+    fn delete_next_char(&mut self) {
+        let start = self.cursor.offset;
+        if let Some(len) = self.buffer.next_grapheme_len(start) {
+            self.buffer.delete(start..start + len, start);
+            self.set_offset(start);
+        }
+    }
+
+    /// Deletes just the last code point before the cursor, like an accent off a letter.
+    fn delete_last_codepoint(&mut self) {
+        let end = self.cursor.offset;
+        if let Some(c) = self.buffer.char_before(end) {
+            let start = end - c.len_utf8();
+            self.buffer.delete(start..end, end);
+            self.set_offset(start);
+        }
+    }
+
+    /// Deletes just the code point under the cursor.
+    fn delete_next_codepoint(&mut self) {
+        let start = self.cursor.offset;
+        if let Some(c) = self.buffer.char_at(start) {
+            self.buffer.delete(start..start + c.len_utf8(), start);
+            self.set_offset(start);
+        }
+    }
+
+    fn undo(&mut self) {
+        if let Some(offset) = self.buffer.undo() {
+            self.set_offset(offset);
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(offset) = self.buffer.redo() {
+            self.set_offset(offset);
+        }
+    }
+
+    fn earlier(&mut self) {
+        if let Some(offset) = self.buffer.earlier() {
+            self.set_offset(offset);
+        }
+    }
+
+    fn later(&mut self) {
+        if let Some(offset) = self.buffer.later() {
+            self.set_offset(offset);
+        }
+    }
+
+    fn earlier_by(&mut self, ago: Duration) {
+        if let Some(offset) = self.buffer.earlier_by(ago) {
+            self.set_offset(offset);
+        }
+    }
+
+    fn later_by(&mut self, later: Duration) {
+        if let Some(offset) = self.buffer.later_by(later) {
+            self.set_offset(offset);
+        }
+    }
+
+    fn move_left(&mut self) {
+        if let Some(len) = self.buffer.prev_grapheme_len(self.cursor.offset) {
+            self.set_offset(self.cursor.offset - len);
+        }
+    }
+
+    fn move_right(&mut self) {
+        if let Some(len) = self.buffer.next_grapheme_len(self.cursor.offset) {
+            self.set_offset(self.cursor.offset + len);
+        }
+    }
+
+    /// Moves up by `rows` rows on screen, which are lines unless they're wrapped.
+    fn move_up(&mut self, rows: usize) {
+        let (mut line, mut row) = (self.cursor.line, self.cursor_row().0);
+        for _ in 0..rows {
+            if row > 0 {
+                row -= 1;
+            } else if line > 0 {
+                line -= 1;
+                row = self.rows(line).len() - 1;
+            } else {
+                break;
+            }
+        }
+        self.set_row_keep_goal(line, row);
+    }
+
+    fn move_down(&mut self, rows: usize) {
+        let (mut line, mut row) = (self.cursor.line, self.cursor_row().0);
+        let mut line_rows = self.rows(line).len();
+        for _ in 0..rows {
+            if row + 1 < line_rows {
+                row += 1;
+            } else if line + 1 < self.buffer.line_count() {
+                line += 1;
+                row = 0;
+                line_rows = self.rows(line).len();
+            } else {
+                break;
+            }
+        }
+        self.set_row_keep_goal(line, row);
+    }
+
+    fn move_home(&mut self) {
+        self.set_offset(self.buffer.line_start(self.cursor.line));
+    }
+
+    fn move_end(&mut self) {
+        let line = self.cursor.line;
+        self.set_offset(self.buffer.line_start(line) + self.buffer.line_len(line));
+    }
+
+    /// Puts the cursor at `offset` and recomputes everything that derives from it.
+    fn set_offset(&mut self, offset: usize) {
+        let line = self.buffer.line_of_offset(offset);
+        let start = self.buffer.line_start(line);
+        // not in the middle of a `\r\n`
+        let col = (offset - start).min(self.buffer.line_len(line));
+
+        self.cursor = Cursor {
+            line,
+            col,
+            offset: start + col,
+            goal_col: 0,
+        };
+        self.cursor.goal_col = self.cursor_row().1;
+    }
+
+    /// The rows `line` takes up on screen: one, unless it's wrapped.
+    fn rows(&self, line: usize) -> Rc<Vec<layout::Row>> {
+        match self.wrap_width {
+            Some(width) => self.buffer.rows(line, self.config.tab_width, width),
+            // no need to lay out the whole line to know that
+            None => Rc::new(vec![layout::Row {
+                start: 0,
+                end: self.buffer.line_len(line),
+                col: 0,
+            }]),
+        }
+    }
+
+    /// Which row of its line the cursor is on, and the column on screen it's at in that row.
+    fn cursor_row(&self) -> (usize, usize) {
+        let rows = self.rows(self.cursor.line);
+        let i = layout::row_of_offset(&rows, self.cursor.col);
+        let row = rows[i];
+
+        (i, row.indent() + self.cursor_col() - row.col)
+    }
+
+    /// The screen column the cursor is at.
+    fn cursor_col(&self) -> usize {
+        self.buffer
+            .col_of_offset(self.cursor.line, self.cursor.col, self.config.tab_width)
+    }
+
+    /// Moves to row `row` of `line`, as close to the goal column as that row allows.
+    fn set_row_keep_goal(&mut self, line: usize, row: usize) {
+        let goal_col = self.cursor.goal_col;
+        let tab_width = self.config.tab_width;
+        let col = if self.wrap_width.is_none() {
+            self.buffer.offset_of_col(line, goal_col, tab_width)
+        } else {
+            let row = self.rows(line)[row];
+            let goal = row.col + goal_col.saturating_sub(row.indent());
+            let start = self.buffer.line_start(line);
+            let text = self.buffer.text.slice(start + row.start..start + row.end);
+            let mut glyphs = layout::glyphs_at(&text, tab_width, row.col);
+            glyphs.skip_to(goal);
+            let col = row.start + glyphs.position().0;
+            // the end of a row that's not the last is the start of the next one, so the closest
+            // the cursor can get on this one is the last character
+            if col == row.end && row.end < self.buffer.line_len(line) {
+                col - self.buffer.prev_grapheme_len(start + col).unwrap_or(0)
+            } else {
+                col
+            }
+        };
+
+        self.cursor = Cursor {
+            line,
+            col,
+            offset: self.buffer.line_start(line) + col,
+            goal_col,
+        };
     }
 }
 
 #[derive(Debug)]
 enum EditorEvent {
     Edited,
+    Moved,
+    Resized,
+    /// A chord was started, continued or abandoned.
+    Chord,
+    /// A message was shown or cleared.
+    Message,
     Quit,
+    /// SIGTERM or SIGHUP came in, so the editor has to go.
+    Terminated,
     Continue,
 }
 
+/// A line of feedback for the bottom of the screen, which stays up until the next key press.
+struct Message {
+    text: String,
+    error: bool,
+}
+
+impl Message {
+    fn info(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            error: false,
+        }
+    }
+
+    fn error(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            error: true,
+        }
+    }
+
+    /// An error message about doing `what` with the file at `path`.
+    fn io_error(what: &str, path: &Path, e: &io::Error) -> Self {
+        // errors from the OS already spell out their kind ("Permission denied (os error 13)"),
+        // the ones made up along the way don't always
+        let text = if e.raw_os_error().is_some() {
+            format!("Couldn't {what} {}: {e}", path.display())
+        } else {
+            format!("Couldn't {what} {}: {}: {e}", path.display(), e.kind())
+        };
+
+        Self { text, error: true }
+    }
+}
+
+/// How long to wait for input before checking for signals.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long after the last key press the swap file gets updated.
+const SWAP_IDLE: Duration = Duration::from_secs(2);
+
+/// How long the swap file can go without an update while keys keep coming.
+const SWAP_MAX_AGE: Duration = Duration::from_secs(30);
+
+/// Puts the terminal back the way it was before the editor started. Errors are ignored, since
+/// this is also what happens when things are already going wrong.
+fn restore_terminal() {
+    let _ = terminal::disable_raw_mode();
+    let _ = execute!(
+        io::stdout(),
+        terminal::LeaveAlternateScreen,
+        Print(POP_TITLE)
+    );
+}
+
+/// Saves the terminal's window title on a stack, so it can be put back when the editor's done
+/// with it. Terminals that don't keep a stack ignore this.
+const PUSH_TITLE: &str = "\x1b[22;0t";
+
+/// Puts back the title `PUSH_TITLE` saved.
+const POP_TITLE: &str = "\x1b[23;0t";
+
 struct Tui {
     out: Stdout,
     editor: Editor,
+    view: Viewport,
+    gutter: Gutter,
+    /// The revision, line ending and lines on screen the line ending signs were last put up for,
+    /// so they're only redone after changes.
+    line_ending_signs: Option<(u64, LineEnding, Range<usize>)>,
+    renderer: Renderer,
+    /// Size of the terminal in (columns, rows).
+    size: (usize, usize),
+    mode: Mode,
+    /// The keys typed so far of a chord that isn't finished yet.
+    pending: Vec<Key>,
+    /// The question being asked in the minibuffer, if any. Keys go to it while it's open.
+    prompt: Option<Prompt>,
+    /// Shown in the minibuffer when there's no prompt.
+    message: Option<Message>,
+    /// Messages waiting their turn, like the problems found at startup. The next one is shown
+    /// after a key press that doesn't bring up a message of its own.
+    queued: VecDeque<Message>,
+    /// What the terminal's window title was last set to.
+    title: String,
+    /// Set when SIGTERM or SIGHUP comes in.
+    terminated: Arc<AtomicBool>,
+    last_key: Instant,
+    /// When the swap file was last brought up to date.
+    last_swap: Instant,
 }
 
 impl Tui {
     fn new(editor: Editor) -> Self {
-        Self {
+        let (cols, rows) = terminal::size().unwrap_or((80, 24));
+        let gutter = Gutter::new(editor.config.line_numbers);
+
+        let mut tui = Self {
             // Crossterm is can write to any buffer that is `Write`, in our case, that's just stdout
             out: std::io::stdout(),
             editor,
+            view: Viewport::default(),
+            gutter,
+            line_ending_signs: None,
+            renderer: Renderer::new(),
+            size: (0, 0),
+            mode: Mode::Edit,
+            pending: Vec::new(),
+            prompt: None,
+            message: None,
+            queued: VecDeque::new(),
+            title: String::new(),
+            terminated: Arc::new(AtomicBool::new(false)),
+            last_key: Instant::now(),
+            last_swap: Instant::now(),
+        };
+        tui.layout(cols, rows);
+
+        tui
+    }
+
+    /// Splits the terminal up between everything that's drawn on it. Called whenever the
+    /// terminal changes size.
+    fn layout(&mut self, cols: u16, rows: u16) {
+        self.size = (cols as usize, rows as usize);
+        self.fit_view();
+        // the terminal may have reflowed or dropped what was on it
+        self.renderer.invalidate();
+    }
+
+    /// Gives the view the part of the screen that's left next to the gutter and above the status
+    /// line. The gutter gets wider as the buffer gets more lines, so this is done before
+    /// each draw too.
+    fn fit_view(&mut self) {
+        let gutter = self.gutter.width(self.editor.buffer.line_count());
+        let width = self.size.0.saturating_sub(gutter);
+        // the bottom two lines are the status line and the minibuffer
+        let height = self.size.1.saturating_sub(2);
+        if (width, height) != (self.view.width, self.view.height) {
+            self.view.resize(width, height);
+            self.update_wrap_width();
+        }
+    }
+
+    /// Puts a sign next to the lines on screen that don't end the way the buffer's supposed to,
+    /// when it has both kinds of line ending. Those are the lines that change when the endings are
+    /// normalized.
+    fn mark_line_endings(&mut self) {
+        let buffer = &self.editor.buffer;
+        if !buffer.endings.is_mixed() {
+            self.gutter.clear_signs("line-ending");
+            self.line_ending_signs = None;
+            return;
+        }
+        // a file can have a lot of lines, so only the ones on screen are looked at
+        let lines = self.view.lines(buffer.line_count());
+        let marked = (buffer.revision, buffer.line_ending, lines.clone());
+        if self.line_ending_signs.as_ref() == Some(&marked) {
+            return;
         }
+
+        let signs = lines
+            .filter(|&line| {
+                buffer
+                    .ending_of(line)
+                    .is_some_and(|ending| ending != buffer.line_ending)
+            })
+            .map(|line| {
+                let sign = Sign {
+                    symbol: '\u{21b5}',
+                    style: ContentStyle::new().yellow(),
+                    priority: 0,
+                };
+                (line, sign)
+            });
+        self.gutter.set_signs("line-ending", signs);
+        self.line_ending_signs = Some(marked);
+    }
+
+    /// Works out how wide the rows are that lines get wrapped onto, which depends on the size of
+    /// the screen.
+    fn update_wrap_width(&mut self) {
+        let config = &self.editor.config;
+        self.editor.wrap_width = config.wrap.then(|| match config.wrap_column {
+            0 => self.view.width,
+            column => column.min(self.view.width),
+        });
     }
 
     fn run(&mut self) {
+        for signal in [SIGTERM, SIGHUP] {
+            // The first signal only sets the flag, and the main loop shuts down properly once it
+            // sees it. If it's stuck somewhere and never does, a second signal kills it outright.
+            flag::register_conditional_shutdown(signal, 1, Arc::clone(&self.terminated))
+                .expect("signal handlers can be registered");
+            flag::register(signal, Arc::clone(&self.terminated))
+                .expect("signal handlers can be registered");
+        }
+
+        // The panic message is printed by the default hook, which would print it onto the
+        // alternate screen in raw mode where it's gone as soon as the terminal is restored. So the
+        // terminal gets restored first.
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore_terminal();
+            default_hook(info);
+        }));
+
         // The "alternate screen" is like another window or tab that you can draw to. When it's closed
         // the user is returned to the regular shell prompt. This is how "full-screen" terminal apps
         // like vim or htop do it.
-        execute!(&self.out, terminal::EnterAlternateScreen).unwrap();
+        execute!(&self.out, terminal::EnterAlternateScreen, Print(PUSH_TITLE)).unwrap();
 
         // By default the terminal acts sort of like the default text input of the shell. By enabling
         // "raw mode" crossterm gives us full control of what and how stuff gets displayed.
         terminal::enable_raw_mode().unwrap();
 
+        let result = panic::catch_unwind(AssertUnwindSafe(|| self.main_loop()));
+        restore_terminal();
+
+        // back on the normal screen, so anything printed now stays visible
+        match result {
+            Ok(EditorEvent::Quit) => {
+                // whatever wasn't saved was deliberately discarded
+                self.editor.buffer.remove_swap();
+                if let Err(e) = self.editor.buffer.flush_swap() {
+                    eprintln!("couldn't remove the swap file: {e}");
+                }
+                self.save_history();
+            }
+            Ok(_) => {
+                let _ = self.editor.buffer.update_swap();
+                let _ = self.editor.buffer.flush_swap();
+                self.rescue();
+                self.save_history();
+                process::exit(1);
+            }
+            // whatever the panic interrupted may have left the history in a state that doesn't
+            // match the text, so it's not saved
+            Err(panic) => {
+                self.rescue();
+                panic::resume_unwind(panic);
+            }
+        }
+    }
+
+    /// Draws and handles input until the editor quits or is terminated, and returns which of
+    /// those it was.
+    fn main_loop(&mut self) -> EditorEvent {
         // first draw
         self.draw();
         // This is the main loop our app runs in.
         loop {
             match self.read_input() {
                 EditorEvent::Continue => continue,
-                EditorEvent::Quit => break,
-                EditorEvent::Edited => {
+                event @ (EditorEvent::Quit | EditorEvent::Terminated) => return event,
+                EditorEvent::Edited
+                | EditorEvent::Moved
+                | EditorEvent::Resized
+                | EditorEvent::Chord
+                | EditorEvent::Message => {
                     self.draw();
                 }
             };
         }
+    }
+
+    /// Updates the swap file when the user has stopped typing for a moment, or it's been a while
+    /// since the last update. Returns whether there's an error message to show.
+    fn update_swap(&mut self) -> bool {
+        let due = self.last_key.elapsed() >= SWAP_IDLE || self.last_swap.elapsed() >= SWAP_MAX_AGE;
+        if !due {
+            return false;
+        }
+        self.last_swap = Instant::now();
 
-        terminal::disable_raw_mode().unwrap();
-        execute!(&self.out, terminal::LeaveAlternateScreen).unwrap();
+        let buffer = &mut self.editor.buffer;
+        match buffer.update_swap() {
+            Ok(()) => false,
+            Err(e) => {
+                // it would most likely fail the same way every time, so it's not tried again
+                if let Some(path) = buffer.swap_path.take() {
+                    self.message = Some(Message::io_error("write the swap file", &path, &e));
+                }
+                true
+            }
+        }
+    }
+
+    fn save_history(&self) {
+        if let Err(e) = self.editor.buffer.save_history() {
+            eprintln!("couldn't save the undo history: {e}");
+        }
+    }
+
+    /// Writes an emergency copy of the buffer if it has unsaved changes, for when the editor is
+    /// going down without a chance to ask what to do with them.
+    fn rescue(&self) {
+        let buffer = &self.editor.buffer;
+        if !buffer.is_modified() {
+            return;
+        }
+
+        let name = buffer.path.state_name();
+        // after SIGHUP there's no terminal left to print to, which is no reason to panic
+        let mut err = io::stderr();
+        let _ = match recovery::save(&name, &buffer.text) {
+            Ok(copy) => writeln!(
+                err,
+                "unsaved changes to {} were written to {}",
+                buffer.path,
+                copy.display()
+            ),
+            Err(e) => writeln!(
+                err,
+                "couldn't write an emergency copy of {}: {e}",
+                buffer.path
+            ),
+        };
+    }
+
+    /// Scrolls so the cursor stays on screen.
+    fn scroll(&mut self) {
+        let editor = &self.editor;
+        let cursor = editor.cursor;
+        if editor.wrap_width.is_some() {
+            let (row, _) = editor.cursor_row();
+            self.view.left = 0;
+            self.view
+                .scroll_to_row(cursor.line, row, |line| editor.rows(line).len());
+        } else {
+            self.view.top_row = 0;
+            self.view.scroll_to(cursor.line, editor.cursor_col());
+        }
     }
 
     fn draw(&mut self) {
-        queue!(
-            &mut self.out,
-            terminal::Clear(terminal::ClearType::All),
-            cursor::MoveTo(0, 0),
-        )
-        .unwrap();
+        let mut frame = Frame::new(self.size.0, self.size.1);
+        self.mark_line_endings();
+        self.fit_view();
+        self.scroll();
+        // again, for the lines that scrolling brought on screen
+        self.mark_line_endings();
 
-        let mut lines = self.editor.buffer.data.lines();
+        if self.editor.wrap_width.is_some() {
+            self.draw_wrapped(&mut frame);
+        } else {
+            self.draw_lines(&mut frame);
+        }
+        self.draw_status(&mut frame);
+        self.draw_minibuffer(&mut frame);
+
+        self.renderer.draw(&mut self.out, frame).unwrap();
+        self.set_title();
+    }
 
-        // print the first line
-        queue!(&mut self.out, Print(lines.next().unwrap_or(""))).unwrap();
+    /// Draws the status line, which is about the buffer and where the cursor is in it.
+    fn draw_status(&self, frame: &mut Frame) {
+        let y = self.size.1.saturating_sub(2);
+        let buffer = &self.editor.buffer;
+        let cursor = self.editor.cursor;
+        let style = ContentStyle::new().reverse();
 
-        // reset the cursor before each subsequent line
-        for line in lines {
-            queue!(&self.out, cursor::MoveToNextLine(1), Print(line),).unwrap();
+        let name = format!(" {}", buffer.path);
+        let mut flags = String::new();
+        if buffer.is_modified() {
+            flags.push_str(" [+]");
+        }
+        if buffer.read_only {
+            flags.push_str(" [read-only]");
         }
 
-        self.out.flush().unwrap();
+        let percent = (cursor.line + 1) * 100 / buffer.line_count();
+        let ending = if buffer.endings.is_mixed() {
+            format!("{} (mixed)", buffer.line_ending)
+        } else {
+            buffer.line_ending.to_string()
+        };
+        let right = format!(
+            "{}:{}  {percent}%  {}  {ending}  {} ",
+            cursor.line + 1,
+            self.editor.cursor_col() + 1,
+            buffer.encoding,
+            self.mode.name(),
+        );
+
+        // the whole line is in reverse video, spaces included
+        frame.put_str(0, y, &" ".repeat(self.size.0), style);
+        // what's on the right, and whether the buffer's modified, are worth more than the end of
+        // a long file name, which is cut off so they fit with a space to spare
+        let x = self.size.0.saturating_sub(layout::width(&right, 1));
+        let room = x.saturating_sub(1);
+        let end = layout::offset_of_col(&name, room.saturating_sub(layout::width(&flags, 1)), 1);
+        let left = format!("{}{flags}", &name[..end]);
+        let end = layout::offset_of_col(&left, room, 1);
+        frame.put_str(0, y, &left[..end], style);
+        frame.put_str(x, y, &right, style);
+    }
+
+    /// Sets the terminal's window title to the name of the buffer, for the window or tab it's in.
+    fn set_title(&mut self) {
+        let buffer = &self.editor.buffer;
+        let name = match buffer.path {
+            BufferPath::File(ref path) => path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            ),
+            BufferPath::Temp(_) => buffer.path.to_string(),
+        };
+        // a file name can have anything in it, and control characters in the title could end
+        // the escape sequence it's sent in early and send the terminal the rest
+        let name: String = name.chars().filter(|c| !c.is_control()).collect();
+        let modified = if buffer.is_modified() { " +" } else { "" };
+        let title = format!("{name}{modified} - edythe");
+
+        if title != self.title {
+            // it doesn't matter much if this doesn't work
+            let _ = execute!(self.out, terminal::SetTitle(&title));
+            self.title = title;
+        }
+    }
+
+    /// Draws the buffer with a line on each row, cut off at the edge of the screen.
+    fn draw_lines(&mut self, frame: &mut Frame) {
+        let buffer = &self.editor.buffer;
+        let cursor = self.editor.cursor;
+        let tab_width = self.editor.config.tab_width;
+        let cursor_col = self.editor.cursor_col();
+
+        // only the lines that fit on screen get drawn, and only the part of each line that's not
+        // scrolled off to the side, which can be a small part of a very long line
+        let (left, width) = (self.view.left, self.view.width);
+        // the text goes to the right of the gutter
+        let x = self.gutter.width(buffer.line_count());
+        let overflow_style = ContentStyle::new().reverse();
+        for (y, line) in self.view.lines(buffer.line_count()).enumerate() {
+            self.gutter
+                .draw(frame, y, Some(line), cursor.line, buffer.line_count());
+
+            let (text, _, col) = buffer.section(line, left..left + width, tab_width);
+            let mut glyphs = layout::glyphs_at(&text, tab_width, col);
+            glyphs.skip_to(left);
+            let cut_off = draw_glyphs(frame, glyphs, y, left, x, x + width);
+
+            // show that there's more of the line off to either side
+            if left > 0 && buffer.line_len(line) > 0 {
+                frame.set(x, y, "<", overflow_style);
+            }
+            if cut_off && width > 1 {
+                frame.set(x + width - 1, y, ">", overflow_style);
+            }
+        }
+
+        // put the terminal's cursor where the editor's cursor is
+        frame.cursor = Some((x + cursor_col - left, cursor.line - self.view.top));
+    }
+
+    /// Draws the buffer with lines that are too long for the screen wrapped onto more rows.
+    fn draw_wrapped(&mut self, frame: &mut Frame) {
+        let editor = &self.editor;
+        let buffer = &editor.buffer;
+        let cursor = editor.cursor;
+        let tab_width = editor.config.tab_width;
+
+        let (cursor_row, cursor_x) = editor.cursor_row();
+
+        let x = self.gutter.width(buffer.line_count());
+        let marker_style = ContentStyle::new().dim();
+        let mut y = 0;
+        // rows of the top line that are scrolled off the top
+        let mut skip = self.view.top_row;
+        'lines: for line in self.view.top..buffer.line_count() {
+            let start = buffer.line_start(line);
+            for (i, &row) in editor.rows(line).iter().enumerate().skip(skip) {
+                if y >= self.view.height {
+                    break 'lines;
+                }
+
+                // only the first row of a line gets its number
+                let number = (i == 0).then_some(line);
+                self.gutter
+                    .draw(frame, y, number, cursor.line, buffer.line_count());
+                if i > 0 {
+                    frame.put_str(x, y, layout::WRAP_MARKER, marker_style);
+                }
+                // only the rows on screen get laid out, which can be a small part of a very long
+                // line
+                let text = buffer.text.slice(start + row.start..start + row.end);
+                let glyphs = layout::glyphs_at(&text, tab_width, row.col);
+                let right = x + self.view.width;
+                draw_glyphs(frame, glyphs, y, row.col, x + row.indent(), right);
+                if line == cursor.line && i == cursor_row {
+                    frame.cursor = Some((x + cursor_x, y));
+                }
+                y += 1;
+            }
+            skip = 0;
+        }
+    }
+
+    /// Draws the bottom line: the prompt if one is open, and the start of a chord while waiting
+    /// for the rest of it.
+    fn draw_minibuffer(&self, frame: &mut Frame) {
+        let y = self.size.1.saturating_sub(1);
+
+        if let Some(ref prompt) = self.prompt {
+            let x = frame.put_str(0, y, &prompt.label, ContentStyle::new().bold());
+            let end = frame.put_str(x, y, &prompt.input, ContentStyle::default());
+            if !prompt.completions.is_empty() {
+                let completions = format!("  {{{}}}", prompt.completions.join(" | "));
+                frame.put_str(end, y, &completions, ContentStyle::new().dim());
+            }
+            let col = layout::col_of_offset(&prompt.input, prompt.cursor, 1);
+            frame.cursor = Some((x + col, y));
+        } else if let Some(ref message) = self.message {
+            let style = if message.error {
+                ContentStyle::new().red().bold()
+            } else {
+                ContentStyle::default()
+            };
+            frame.put_str(0, y, &message.text, style);
+        }
+
+        if !self.pending.is_empty() {
+            let keys: Vec<String> = self.pending.iter().map(Key::to_string).collect();
+            let indicator = format!(" {}- ", keys.join(" "));
+            let x = self.size.0.saturating_sub(layout::width(&indicator, 1));
+            frame.put_str(x, y, &indicator, ContentStyle::new().reverse());
+        }
     }
 
     fn read_input(&mut self) -> EditorEvent {
-        match event::read().unwrap() {
+        // wait in short stretches, to notice signals in between
+        loop {
+            if self.terminated.load(Ordering::Relaxed) {
+                return EditorEvent::Terminated;
+            }
+            match event::poll(POLL_INTERVAL) {
+                Ok(true) => break,
+                Ok(false) => {
+                    if self.update_swap() {
+                        return EditorEvent::Message;
+                    }
+                }
+                // the wait was cut short by a signal
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => panic!("couldn't read from the terminal: {e}"),
+            }
+        }
+
+        let event = match event::read().unwrap() {
             Event::Key(key_event) => self.match_keyevent(key_event),
-            Event::Resize(_, _) => EditorEvent::Continue, // TODO
-            Event::Mouse(_) => EditorEvent::Continue,     // TODO
+            Event::Resize(cols, rows) => {
+                self.layout(cols, rows);
+                EditorEvent::Resized
+            }
+            Event::Mouse(_) => EditorEvent::Continue, // TODO
             _ => EditorEvent::Continue,
+        };
+
+        // keys that keep coming never let the wait above time out, so the swap file would only
+        // get updated once they stopped if it weren't for this
+        match event {
+            EditorEvent::Continue if self.update_swap() => EditorEvent::Message,
+            event => {
+                self.update_swap();
+                event
+            }
         }
     }
 
     fn match_keyevent(&mut self, key_event: KeyEvent) -> EditorEvent {
-        match key_event {
-            KeyEvent {
-                code: KeyCode::Char('c'),
-                modifiers: KeyModifiers::CONTROL,
-                ..
-            } => return EditorEvent::Quit,
-            KeyEvent {
-                code: KeyCode::Char('s'),
-                modifiers: KeyModifiers::CONTROL,
-                ..
-            } => self
-                .editor
-                .save_to_disk()
-                .expect("I couldn't save the file for some reason."),
-            KeyEvent {
-                code: KeyCode::Backspace,
-                ..
-            } => self.editor.delete_last_char(),
-            KeyEvent {
-                code: KeyCode::Char(c),
-                ..
-            } => self.editor.insert_char(c),
+        // terminals that speak the kitty keyboard protocol report key releases too
+        if key_event.kind == KeyEventKind::Release {
+            return EditorEvent::Continue;
+        }
+
+        self.last_key = Instant::now();
+        // a message stays up until the next key press
+        let mut message_changed = self.message.take().is_some();
+
+        self.pending.push(Key::from(key_event));
+        let event = match self.editor.config.keymap.lookup(self.mode, &self.pending) {
+            Lookup::Command(command) => {
+                self.pending.clear();
+                self.run_command(command)
+            }
+            Lookup::Pending => EditorEvent::Chord,
+            Lookup::Unbound => {
+                let keys = std::mem::take(&mut self.pending);
+                match keys[..] {
+                    // keys that aren't bound to anything type their character, if they have one
+                    [key] => match key.text() {
+                        Some(c) => self.type_char(c),
+                        None => EditorEvent::Continue,
+                    },
+                    // a chord that went nowhere; it's dropped, and so is its indicator
+                    _ => EditorEvent::Chord,
+                }
+            }
+        };
+
+        if self.message.is_none() && !self.queued.is_empty() {
+            self.message = self.queued.pop_front();
+            message_changed = true;
+        }
+
+        match event {
+            EditorEvent::Continue if message_changed => EditorEvent::Message,
+            event => event,
+        }
+    }
+
+    fn type_char(&mut self, c: char) -> EditorEvent {
+        match self.prompt {
+            Some(ref prompt) if prompt.purpose.is_choice() => return self.answer_prompt(c),
+            Some(ref mut prompt) => prompt.insert_char(c),
+            None => self.editor.insert_char(c),
+        }
+
+        EditorEvent::Edited
+    }
+
+    fn open_prompt(&mut self, prompt: Prompt) {
+        self.prompt = Some(prompt);
+        self.mode = Mode::Prompt;
+    }
+
+    fn close_prompt(&mut self) -> Option<Prompt> {
+        self.mode = Mode::Edit;
+        self.prompt.take()
+    }
+
+    /// Saves the buffer, asking for a file name first if it doesn't have one. With `quit`, the
+    /// editor quits once the buffer is saved.
+    fn save(&mut self, quit: bool) -> EditorEvent {
+        match self.editor.buffer.path {
+            BufferPath::File(ref path) if self.editor.buffer.read_only => {
+                let text = format!(
+                    "{} is read-only, use save-as to save it elsewhere",
+                    path.display()
+                );
+                self.message = Some(Message::error(text));
+                EditorEvent::Edited
+            }
+            BufferPath::File(ref path) => {
+                let path = path.clone();
+                let result = self.editor.save_to_disk();
+                self.saved(result, &path, quit)
+            }
+            BufferPath::Temp(_) => {
+                self.prompt_save_as(quit);
+                EditorEvent::Edited
+            }
+        }
+    }
+
+    /// Saves the buffer to `path`. With `quit`, the editor quits afterwards.
+    fn save_as(&mut self, path: PathBuf, quit: bool) -> EditorEvent {
+        let result = self.editor.save_as(path.clone());
+        self.saved(result, &path, quit)
+    }
+
+    /// Reports how saving to `path` went. If it failed the editor keeps running, even when it was
+    /// supposed to quit, so the changes aren't lost.
+    fn saved(&mut self, result: io::Result<()>, path: &Path, quit: bool) -> EditorEvent {
+        match result {
+            Ok(()) if quit => EditorEvent::Quit,
+            Ok(()) => {
+                self.message = Some(Message::info(format!("Saved {}", path.display())));
+                EditorEvent::Edited
+            }
+            Err(e) => {
+                self.message = Some(Message::io_error("save", path, &e));
+                EditorEvent::Edited
+            }
+        }
+    }
+
+    /// Asks for a file name to save to, starting from the current one if there is one.
+    fn prompt_save_as(&mut self, quit: bool) {
+        let mut prompt = Prompt::new("Save as: ", Purpose::SaveAs { quit });
+        if let BufferPath::File(ref path) = self.editor.buffer.path {
+            prompt.input = path.to_string_lossy().into_owned();
+            prompt.move_end();
+        }
+        self.open_prompt(prompt);
+    }
+
+    /// Quits, unless there are unsaved changes, in which case it asks what to do about them
+    /// first.
+    fn quit(&mut self) -> EditorEvent {
+        if !self.editor.buffer.is_modified() {
+            return EditorEvent::Quit;
+        }
+
+        let label = format!(
+            "{} has unsaved changes: (s)ave, (d)iscard or (c)ancel? ",
+            self.editor.buffer.path
+        );
+        self.open_prompt(Prompt::new(label, Purpose::Quit));
+
+        EditorEvent::Edited
+    }
+
+    /// Does what the prompt was asking about, with the text that was typed into it.
+    fn accept_prompt(&mut self) -> EditorEvent {
+        let Some(prompt) = self.close_prompt() else {
+            return EditorEvent::Continue;
+        };
+
+        match prompt.purpose {
+            Purpose::SaveAs { .. } if prompt.input.is_empty() => EditorEvent::Edited,
+            Purpose::SaveAs { quit } => {
+                let path = expand_path(&prompt.input);
+                let same = matches!(self.editor.buffer.path, BufferPath::File(ref p) if *p == path);
+                if path.exists() && !same {
+                    let label = format!("{} exists, overwrite it? (y/n) ", path.display());
+                    self.open_prompt(Prompt::new(label, Purpose::Overwrite { path, quit }));
+                    EditorEvent::Edited
+                } else {
+                    self.save_as(path, quit)
+                }
+            }
+            Purpose::Encoding => {
+                let buffer = &mut self.editor.buffer;
+                let text = match Encoding::from_name(&prompt.input) {
+                    None => format!("There's no encoding called {:?}", prompt.input.trim()),
+                    Some(encoding) => match buffer.set_encoding(encoding) {
+                        Ok(()) => {
+                            self.message = Some(Message::info(format!(
+                                "{} will be saved in {encoding}",
+                                buffer.path
+                            )));
+                            return EditorEvent::Edited;
+                        }
+                        Err(c) => format!("{c:?} can't be written in {encoding}"),
+                    },
+                };
+                self.message = Some(Message::error(text));
+                EditorEvent::Edited
+            }
+            Purpose::LineEnding => {
+                let buffer = &mut self.editor.buffer;
+                self.message = Some(match LineEnding::from_name(&prompt.input) {
+                    Some(ending) => {
+                        buffer.line_ending = ending;
+                        // with nothing to change, there's nothing to save either
+                        buffer.normalize_endings = buffer.endings.other_than(ending) > 0;
+                        Message::info(if buffer.normalize_endings {
+                            format!("All lines will end in {ending} once saved")
+                        } else {
+                            format!("All lines already end in {ending}")
+                        })
+                    }
+                    None => Message::error(format!(
+                        "Line endings are lf or crlf, not {:?}",
+                        prompt.input.trim()
+                    )),
+                });
+                EditorEvent::Edited
+            }
+            Purpose::Earlier | Purpose::Later => {
+                let input = prompt.input.trim();
+                // the label offers one minute for when nothing's typed
+                let minutes = if input.is_empty() {
+                    Ok(1)
+                } else {
+                    input.parse::<u64>()
+                };
+                match minutes {
+                    Ok(minutes) => {
+                        let by = Duration::from_secs(minutes.saturating_mul(60));
+                        if prompt.purpose == Purpose::Earlier {
+                            self.editor.earlier_by(by);
+                        } else {
+                            self.editor.later_by(by);
+                        }
+                    }
+                    Err(_) => {
+                        self.message = Some(Message::error(format!(
+                            "{input:?} isn't a number of minutes"
+                        )));
+                    }
+                }
+                EditorEvent::Edited
+            }
+            // these are answered with a single key, so there's nothing to accept
+            Purpose::Overwrite { .. } | Purpose::Quit => {
+                self.open_prompt(prompt);
+                EditorEvent::Continue
+            }
+        }
+    }
+
+    /// Answers a prompt that asks to choose with a single key.
+    fn answer_prompt(&mut self, c: char) -> EditorEvent {
+        let Some(purpose) = self.prompt.as_ref().map(|prompt| prompt.purpose.clone()) else {
+            return EditorEvent::Continue;
+        };
+
+        let answer = c.to_ascii_lowercase();
+        match (purpose, answer) {
+            (Purpose::Overwrite { path, quit }, 'y') => {
+                self.close_prompt();
+                self.save_as(path, quit)
+            }
+            (Purpose::Quit, 's') => {
+                self.close_prompt();
+                self.save(true)
+            }
+            (Purpose::Quit, 'd') => EditorEvent::Quit,
+            (Purpose::Overwrite { .. }, 'n') | (Purpose::Quit, 'c') => {
+                self.close_prompt();
+                EditorEvent::Edited
+            }
+            // not one of the choices
+            _ => EditorEvent::Continue,
+        }
+    }
+
+    /// Runs `command` on the prompt, which only some commands make sense for.
+    fn run_prompt_command(&mut self, command: Command) -> EditorEvent {
+        if command == Command::Accept {
+            return self.accept_prompt();
+        }
+        if command == Command::Cancel {
+            self.close_prompt();
+            return EditorEvent::Edited;
+        }
+
+        let Some(ref mut prompt) = self.prompt else {
+            return EditorEvent::Continue;
+        };
+        match command {
+            Command::Complete if prompt.purpose == Purpose::Encoding => {
+                prompt.complete_from(Encoding::ALL.iter().map(|encoding| encoding.name()))
+            }
+            Command::Complete if prompt.purpose == Purpose::LineEnding => {
+                prompt.complete_from(LineEnding::ALL.iter().map(|ending| ending.name()))
+            }
+            Command::Complete => prompt.complete_path(),
+            Command::DeleteBackward => prompt.delete_backward(),
+            Command::DeleteForward => prompt.delete_forward(),
+            Command::MoveLeft => prompt.move_left(),
+            Command::MoveRight => prompt.move_right(),
+            Command::MoveHome => prompt.move_home(),
+            Command::MoveEnd => prompt.move_end(),
             _ => return EditorEvent::Continue,
         }
 
         EditorEvent::Edited
     }
+
+    fn run_command(&mut self, command: Command) -> EditorEvent {
+        if self.mode == Mode::Prompt {
+            return self.run_prompt_command(command);
+        }
+
+        let page = self.view.height.max(1);
+        match command {
+            Command::Quit => return self.quit(),
+            Command::Save => return self.save(false),
+            Command::SaveAs => self.prompt_save_as(false),
+            Command::SetEncoding => {
+                let mut prompt = Prompt::new("Encoding: ", Purpose::Encoding);
+                prompt.input = self.editor.buffer.encoding.name().to_string();
+                prompt.move_end();
+                self.open_prompt(prompt);
+            }
+            Command::SetLineEnding => {
+                let mut prompt = Prompt::new("Line endings: ", Purpose::LineEnding);
+                prompt.input = self.editor.buffer.line_ending.name().to_string();
+                prompt.move_end();
+                self.open_prompt(prompt);
+            }
+            Command::ToggleWrap => {
+                self.editor.config.wrap = !self.editor.config.wrap;
+                self.update_wrap_width();
+                // the goal column counts from the start of the row, which just changed
+                self.editor.set_offset(self.editor.cursor.offset);
+            }
+            // these are for the prompt
+            Command::Accept | Command::Cancel | Command::Complete => return EditorEvent::Continue,
+            Command::Undo => self.editor.undo(),
+            Command::Redo => self.editor.redo(),
+            Command::Earlier => self.editor.earlier(),
+            Command::Later => self.editor.later(),
+            Command::EarlierBy => self.open_prompt(Prompt::new(
+                "Go back how many minutes? (1) ",
+                Purpose::Earlier,
+            )),
+            Command::LaterBy => self.open_prompt(Prompt::new(
+                "Go forward how many minutes? (1) ",
+                Purpose::Later,
+            )),
+            Command::InsertNewline => self.editor.insert_newline(),
+            Command::InsertTab => self.editor.insert_tab(),
+            Command::DeleteBackward => self.editor.delete_last_char(),
+            Command::DeleteForward => self.editor.delete_next_char(),
+            Command::DeleteCodepointBackward => self.editor.delete_last_codepoint(),
+            Command::DeleteCodepointForward => self.editor.delete_next_codepoint(),
+            Command::MoveLeft => self.editor.move_left(),
+            Command::MoveRight => self.editor.move_right(),
+            Command::MoveUp => self.editor.move_up(1),
+            Command::MoveDown => self.editor.move_down(1),
+            Command::MoveHome => self.editor.move_home(),
+            Command::MoveEnd => self.editor.move_end(),
+            Command::PageUp => self.editor.move_up(page),
+            Command::PageDown => self.editor.move_down(page),
+        }
+
+        match command {
+            Command::MoveLeft
+            | Command::MoveRight
+            | Command::MoveUp
+            | Command::MoveDown
+            | Command::MoveHome
+            | Command::MoveEnd
+            | Command::PageUp
+            | Command::PageDown
+            | Command::ToggleWrap => EditorEvent::Moved,
+            _ => EditorEvent::Edited,
+        }
+    }
+}
+
+/// Draws `glyphs` on row `y` of `frame`, with column `from` of the line at `x`, up to `right`.
+/// Anything that's cut off by either edge isn't drawn. Returns whether anything was cut off on the
+/// right.
+fn draw_glyphs<'a>(
+    frame: &mut Frame,
+    glyphs: impl Iterator<Item = Glyph<'a>>,
+    y: usize,
+    from: usize,
+    x: usize,
+    right: usize,
+) -> bool {
+    let (left, right) = (from, from + right.saturating_sub(x));
+    for glyph in glyphs {
+        if glyph.col >= right {
+            return true;
+        }
+        let style = match glyph.kind {
+            layout::Kind::Escape => ContentStyle::new().blue(),
+            _ => ContentStyle::default(),
+        };
+        // half a wide character can't be drawn, so one that's cut off by the edge of the screen
+        // is left blank
+        let whole = glyph.col >= left && glyph.col + glyph.width <= right;
+        for i in 0..glyph.width {
+            let col = glyph.col + i;
+            if (left..right).contains(&col) {
+                let symbol = if whole || glyph.kind != layout::Kind::Text {
+                    glyph.cell(i)
+                } else {
+                    " "
+                };
+                frame.set(x + col - left, y, symbol, style);
+            }
+        }
+        if glyph.col + glyph.width > right {
+            return true;
+        }
+    }
+
+    false
 }
 
 // Define the command line arguments
@@ -178,25 +1936,311 @@ struct Args {
     file: Option<PathBuf>,
 }
 
+/// Opens the file at `path` in a buffer, along with anything the user should know about how that
+/// went. A file that doesn't exist yet is a new one; one that exists but can't be read is an error
+/// the editor can't start with, since an empty buffer in its place could be saved over it.
+fn open(path: PathBuf) -> (Buffer, Option<Message>) {
+    let bytes = match std::fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let notice = Message::info(format!("New file {}", path.display()));
+            return (
+                Buffer::new(BufferPath::File(path), String::new()),
+                Some(notice),
+            );
+        }
+        Err(e) => {
+            eprintln!("couldn't open {}: {e}", path.display());
+            process::exit(1);
+        }
+    };
+
+    let encoding = Encoding::detect(&bytes);
+    let (mut buffer, notice) = match encoding.decode(&bytes) {
+        Ok(data) => {
+            let read_only = !save::is_writable(&path);
+            let notice =
+                read_only.then(|| Message::info(format!("{} is read-only", path.display())));
+            let mut buffer = Buffer::new(BufferPath::File(path), data);
+            buffer.read_only = read_only;
+            (buffer, notice)
+        }
+        // saving the text as it's shown would replace the bytes that couldn't be decoded for good
+        Err(e) => {
+            let notice = Message::error(format!(
+                "{} isn't valid {encoding} ({e}), so it's read-only and the invalid bytes show as \u{fffd}",
+                path.display()
+            ));
+            let data = encoding.decode_lossy(&bytes);
+            let mut buffer = Buffer::new(BufferPath::File(path), data);
+            buffer.read_only = true;
+            (buffer, Some(notice))
+        }
+    };
+    buffer.encoding = encoding;
+    buffer.saved_encoding = encoding;
+
+    (buffer, notice)
+}
+
+/// Checks for a swap file left behind by an editor that didn't exit cleanly, and if there is one
+/// with changes in it, shows them and asks whether to recover them. Returns anything that's worth
+/// telling once the editor is up.
+fn offer_recovery(buffer: &mut Buffer) -> Option<Message> {
+    let path = buffer.swap_path.clone()?;
+    let swap = match swap::read(&path) {
+        Ok(Some(swap)) => swap,
+        Ok(None) => return None,
+        Err(e) => return Some(Message::io_error("read the swap file", &path, &e)),
+    };
+
+    if swap::is_running(swap.pid) {
+        buffer.swap_path = None;
+        return Some(Message::error(format!(
+            "{} is also open in process {}, so this one won't keep a swap file for it",
+            buffer.path, swap.pid
+        )));
+    }
+
+    let text: String = buffer.text.chunks().collect();
+    if swap.text == text {
+        let _ = swap::remove(&path);
+        return None;
+    }
+
+    let what = format!("unsaved changes to {}", buffer.path);
+    ask_to_recover(buffer, &what, &path, &swap, &text)
+}
+
+/// Offers the text of scratch buffers from editors that didn't exit cleanly, newest first, until
+/// one is recovered into `buffer`, a new scratch buffer. Their swap files are named after the
+/// process they were in, so nothing else would ever come across them. Returns anything that's
+/// worth telling once the editor is up.
+fn offer_scratch_recovery(buffer: &mut Buffer) -> Vec<Message> {
+    let paths = match swap::find("scratch-") {
+        Ok(paths) => paths,
+        Err(e) => return vec![Message::error(format!("Couldn't look for swap files: {e}"))],
+    };
+
+    let mut messages = Vec::new();
+    let mut swaps = Vec::new();
+    for path in paths {
+        match swap::read(&path) {
+            Ok(Some(swap)) if !swap::is_running(swap.pid) => swaps.push((path, swap)),
+            Ok(_) => {}
+            Err(e) => messages.push(Message::io_error("read the swap file", &path, &e)),
+        }
+    }
+    swaps.sort_by_key(|(_, swap)| std::cmp::Reverse(swap.modified));
+
+    for (path, swap) in swaps {
+        if swap.text.is_empty() {
+            let _ = swap::remove(&path);
+            continue;
+        }
+        messages.extend(ask_to_recover(
+            buffer,
+            "unsaved changes in a scratch buffer",
+            &path,
+            &swap,
+            "",
+        ));
+        if buffer.is_modified() {
+            break;
+        }
+    }
+
+    messages
+}
+
+/// Shows what's in `swap`, found at `path`, compared to `text`, the buffer's text, and asks
+/// whether to recover it. `what` says what it is.
+fn ask_to_recover(
+    buffer: &mut Buffer,
+    what: &str,
+    path: &Path,
+    swap: &Swap,
+    text: &str,
+) -> Option<Message> {
+    let ago = swap.modified.elapsed().unwrap_or_default().as_secs();
+    eprintln!(
+        "Found {what} from {} ago, in an editor that didn't exit cleanly:\n",
+        if ago == 1 {
+            "1 second".to_string()
+        } else if ago < 120 {
+            format!("{ago} seconds")
+        } else {
+            format!("{} minutes", ago / 60)
+        }
+    );
+    eprint!("{}", diff::unified(&diff::diff(text, &swap.text), 3));
+
+    loop {
+        eprint!("\nRecover them? (y)es, (n)o and delete them, or (q)uit: ");
+        let mut answer = String::new();
+        // nobody there to answer; leave everything as it is
+        if io::stdin().read_line(&mut answer).unwrap_or(0) == 0 {
+            process::exit(1);
+        }
+
+        match answer.trim() {
+            "y" | "Y" => {
+                buffer.replace_text(&swap.text);
+                if buffer.swap_path.as_deref() == Some(path) {
+                    return None;
+                }
+                // it's only safe to let go of a swap file somewhere else once the text is in
+                // the buffer's own
+                if let Err(e) = buffer.update_swap().and_then(|()| buffer.flush_swap()) {
+                    return buffer
+                        .swap_path
+                        .as_ref()
+                        .map(|own| Message::io_error("write the swap file", own, &e));
+                }
+                return swap::remove(path)
+                    .err()
+                    .map(|e| Message::io_error("remove the swap file", path, &e));
+            }
+            "n" | "N" => {
+                return swap::remove(path)
+                    .err()
+                    .map(|e| Message::io_error("remove the swap file", path, &e));
+            }
+            "q" | "Q" => process::exit(0),
+            _ => {}
+        }
+    }
+}
+
 fn main() {
     let args = Args::parse();
 
+    // the terminal is about to be taken over, so anything worth telling waits for the message line
+    let mut messages = Vec::new();
     let buffer = match args.file {
         Some(path) => {
-            // read file content into buffer; or empty string if the file doesn't exist
-            let data = std::fs::read_to_string(&path).unwrap_or_default();
+            let (mut buffer, notice) = open(path);
+            messages.extend(notice);
+            if let Err(e) = buffer.load_history() {
+                messages.push(Message::error(format!(
+                    "Couldn't load the undo history for {}: {e}",
+                    buffer.path
+                )));
+            }
+            messages.extend(offer_recovery(&mut buffer));
 
-            Buffer::new(BufferPath::File(path), data)
+            buffer
+        }
+        None => {
+            let mut buffer = Buffer::new(BufferPath::Temp(0), String::new());
+            messages.extend(offer_scratch_recovery(&mut buffer));
+
+            buffer
         }
-        None => Buffer {
-            path: BufferPath::Temp(0),
-            data: String::new(),
-        },
     };
 
-    let editor = Editor::new(buffer);
+    let (config, problems) = Config::load();
+    messages.extend(problems.into_iter().map(Message::error));
+
+    let editor = Editor::new(buffer, config);
 
     let mut tui = Tui::new(editor);
+    tui.queued.extend(messages);
+    tui.message = tui.queued.pop_front();
 
     tui.run();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Random;
+
+    #[test]
+    fn grapheme_lens_on_a_long_line() {
+        // long runs of flags and joined emoji, where the nearest boundary that doesn't depend on
+        // what came before is a long way back, going over the rope's leaves
+        let pieces = [
+            "🇫🇷",
+            "🇩",
+            "👨\u{200d}👩\u{200d}👧",
+            "\u{200d}",
+            "e\u{301}",
+            "\u{301}",
+            "\r\n",
+            "\r",
+            "\u{1100}\u{1161}\u{11a8}",
+            "\u{600}",
+            "\u{915}\u{93f}",
+            "x",
+        ];
+        let mut random = Random(7);
+        let mut text = String::new();
+        while text.len() < 8 * 1024 {
+            let piece = random.pick(&pieces);
+            // runs of the same thing, so there's not always a boundary close by
+            for _ in 0..random.below(40) {
+                text.push_str(piece);
+            }
+        }
+        let buffer = Buffer::new(BufferPath::Temp(0), text.clone());
+
+        // wherever it starts, in the middle of a cluster or not, it's the same as going through
+        // the whole text from the start
+        for (offset, _) in text.char_indices() {
+            assert_eq!(
+                buffer.next_grapheme_len(offset),
+                Some(unicode::next_boundary(&text, offset) - offset),
+                "next at {offset}"
+            );
+        }
+        assert_eq!(buffer.next_grapheme_len(text.len()), None);
+        for offset in text.char_indices().map(|(i, c)| i + c.len_utf8()) {
+            assert_eq!(
+                buffer.prev_grapheme_len(offset),
+                Some(offset - unicode::prev_boundary(&text, offset)),
+                "prev at {offset}"
+            );
+        }
+        assert_eq!(buffer.prev_grapheme_len(0), None);
+    }
+
+    #[test]
+    fn line_ending_counts() {
+        let mut random = Random(3);
+        let mut buffer = Buffer::new(BufferPath::Temp(0), "a\r\nb\nc\r\n".to_string());
+        for round in 0..2000 {
+            let text: String = buffer.text.chunks().collect();
+            match random.below(6) {
+                // inserts and deletes can go anywhere, including in between a \r and a \n, and
+                // can leave a \r and a \n next to each other that weren't before
+                0 | 1 => {
+                    let offset = random.offset(&text);
+                    let piece = random.pick(&["\r", "\n", "\r\n", "\n\r", "x", "é"]);
+                    buffer.insert(offset, piece, offset);
+                }
+                2 | 3 => {
+                    let start = random.offset(&text);
+                    let len = text[start..]
+                        .chars()
+                        .take(random.below(4))
+                        .map(char::len_utf8);
+                    buffer.delete(start..start + len.sum::<usize>(), start);
+                }
+                4 => {
+                    buffer.undo();
+                }
+                _ => {
+                    buffer.redo();
+                }
+            }
+
+            let text: String = buffer.text.chunks().collect();
+            assert_eq!(
+                buffer.endings,
+                line_ending::Counts::of(&text),
+                "after round {round}: {text:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! The minibuffer: a line at the bottom of the screen where the editor asks for something, like a
+//! file name to save to. While it's open, keys go to it instead of the buffer.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+
+/// What the prompt is asking for, which decides what happens with the answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Purpose {
+    /// A path to save the buffer to, and whether to quit once it's saved.
+    SaveAs { quit: bool },
+    /// Whether it's okay to save over the file that's already at `path` (y/n).
+    Overwrite { path: PathBuf, quit: bool },
+    /// What to do with unsaved changes before quitting (save/discard/cancel).
+    Quit,
+    /// The encoding to save the buffer in from now on.
+    Encoding,
+    /// The line ending to change all lines to on the next save.
+    LineEnding,
+    /// How many minutes to go back in the undo history.
+    Earlier,
+    /// How many minutes to go forward in the undo history.
+    Later,
+}
+
+impl Purpose {
+    /// Whether the prompt is answered with a single key instead of a line of text.
+    pub fn is_choice(&self) -> bool {
+        matches!(self, Purpose::Overwrite { .. } | Purpose::Quit)
+    }
+}
+
+pub struct Prompt {
+    pub label: String,
+    pub input: String,
+    /// Byte offset of the cursor in `input`.
+    pub cursor: usize,
+    pub purpose: Purpose,
+    /// The candidates from the last tab completion, when there was more than one.
+    pub completions: Vec<String>,
+}
+
+impl Prompt {
+    pub fn new(label: impl Into<String>, purpose: Purpose) -> Self {
+        Self {
+            label: label.into(),
+            input: String::new(),
+            cursor: 0,
+            purpose,
+            completions: Vec::new(),
+        }
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.input.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+        self.completions.clear();
+    }
+
+    pub fn delete_backward(&mut self) {
+        if let Some(c) = self.input[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+            self.input.remove(self.cursor);
+            self.completions.clear();
+        }
+    }
+
+    pub fn delete_forward(&mut self) {
+        if self.cursor < self.input.len() {
+            self.input.remove(self.cursor);
+            self.completions.clear();
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        if let Some(c) = self.input[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if let Some(c) = self.input[self.cursor..].chars().next() {
+            self.cursor += c.len_utf8();
+        }
+    }
+
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn move_end(&mut self) {
+        self.cursor = self.input.len();
+    }
+
+    /// Completes the input as a path, as far as it can be completed without guessing. If that
+    /// still leaves more than one file it could be, they're kept in `completions` for showing.
+    pub fn complete_path(&mut self) {
+        // everything up to the last separator is the directory to look in, the rest is the start
+        // of a name in it
+        let split = self.input.rfind(MAIN_SEPARATOR).map_or(0, |i| i + 1);
+        let (dir, prefix) = self.input.split_at(split);
+        let search = if dir.is_empty() {
+            PathBuf::from(".")
+        } else {
+            expand_path(dir)
+        };
+        let Ok(entries) = fs::read_dir(search) else {
+            return;
+        };
+
+        let mut matches: Vec<String> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let mut name = entry.file_name().into_string().ok()?;
+                if !name.starts_with(prefix) || (prefix.is_empty() && name.starts_with('.')) {
+                    return None;
+                }
+                if entry.file_type().ok()?.is_dir() {
+                    name.push(MAIN_SEPARATOR);
+                }
+                Some(name)
+            })
+            .collect();
+        matches.sort();
+
+        let dir = dir.to_string();
+        self.complete_with(&dir, matches);
+    }
+
+    /// Completes the whole input as one of `candidates`, the same way `complete_path` does.
+    pub fn complete_from<'a>(&mut self, candidates: impl IntoIterator<Item = &'a str>) {
+        let matches = candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(&self.input))
+            .map(str::to_string)
+            .collect();
+        self.complete_with("", matches);
+    }
+
+    /// Replaces the input with `before` followed by as much as all of `matches` have in common.
+    fn complete_with(&mut self, before: &str, matches: Vec<String>) {
+        let Some(first) = matches.first() else {
+            return;
+        };
+        let common = matches.iter().fold(first.as_str(), |common, name| {
+            let len = common
+                .char_indices()
+                .zip(name.chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(common.len().min(name.len()), |((i, _), _)| i);
+            &common[..len]
+        });
+
+        self.input = format!("{before}{common}");
+        self.cursor = self.input.len();
+        self.completions = if matches.len() > 1 {
+            matches
+        } else {
+            Vec::new()
+        };
+    }
+}
+
+/// Turns what was typed into a path, expanding a leading `~` to the home directory.
+pub fn expand_path(input: &str) -> PathBuf {
+    let home = || env::var_os("HOME").map(PathBuf::from);
+    match input.strip_prefix('~') {
+        Some("") => home().unwrap_or_else(|| PathBuf::from(input)),
+        Some(rest) if rest.starts_with(MAIN_SEPARATOR) => match home() {
+            Some(home) => home.join(Path::new(&rest[1..])),
+            None => PathBuf::from(input),
+        },
+        _ => PathBuf::from(input),
+    }
+}
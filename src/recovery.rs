@@ -0,0 +1,42 @@
+//! Emergency copies. When the editor goes down without being asked to (it panicked, or got
+//! SIGTERM or SIGHUP), the text of buffers with unsaved changes is written to the recovery
+//! directory so the changes aren't lost along with it.
+
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use std::time::SystemTime;
+
+use crate::dirs;
+use crate::rope::Rope;
+use crate::save;
+
+/// `$XDG_STATE_HOME/edythe/recovery`.
+pub fn recovery_dir() -> Option<PathBuf> {
+    Some(dirs::state_dir()?.join("recovery"))
+}
+
+/// Writes `text` to a new file in the recovery directory and returns where it went. `name` says
+/// which buffer the text is from; the time and process id are added on, so copies from different
+/// crashes don't overwrite each other.
+pub fn save(name: &str, text: &Rope) -> io::Result<PathBuf> {
+    let path = new_path(name)?;
+    let text: String = text.chunks().collect();
+    save::write_file(&path, text.as_bytes())?;
+
+    Ok(path)
+}
+
+/// Where a new copy of something named `name` goes in the recovery directory, which is made if
+/// it's not there yet.
+pub fn new_path(name: &str) -> io::Result<PathBuf> {
+    let dir = recovery_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no state directory"))?;
+    std::fs::create_dir_all(&dir)?;
+
+    let secs = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+
+    Ok(dir.join(format!("{name}~{secs}-{}", process::id())))
+}
@@ -0,0 +1,210 @@
+//! Drawing without flicker. Instead of clearing the terminal and printing everything again after
+//! every keypress, each frame is first drawn into a grid of cells in memory. That grid is compared
+//! with the one that's on the terminal from last time, and only the cells that changed get sent.
+
+use std::env;
+use std::io::{self, Write};
+
+use crossterm::{
+    cursor, queue,
+    style::{Attribute, ContentStyle, Print, SetAttribute, SetStyle},
+    terminal,
+};
+
+use crate::layout;
+
+/// One character cell on the terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    /// What's printed in the cell. Characters that take up two cells (like most CJK) go in the
+    /// left one, and the right one is left empty.
+    pub symbol: String,
+    pub style: ContentStyle,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            symbol: " ".to_string(),
+            style: ContentStyle::default(),
+        }
+    }
+}
+
+/// Everything that's on the terminal at one point in time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+    /// Where the terminal's cursor goes, if it should be shown at all.
+    pub cursor: Option<(usize, usize)>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::default(); width * height],
+            cursor: None,
+        }
+    }
+
+    /// Puts `symbol` into the cell at `x`, `y`. Anything outside the frame is cut off, and so is
+    /// what's left of a wide character that's partly written over, since half of one can't be
+    /// shown.
+    pub fn set(&mut self, x: usize, y: usize, symbol: &str, style: ContentStyle) {
+        if x < self.width && y < self.height {
+            let i = y * self.width + x;
+            if !symbol.is_empty() {
+                if x + 1 < self.width && self.cells[i + 1].symbol.is_empty() {
+                    self.cells[i + 1].symbol.push(' ');
+                }
+                if x > 0 && self.cells[i].symbol.is_empty() {
+                    self.cells[i - 1].symbol.replace_range(.., " ");
+                }
+            }
+
+            let cell = &mut self.cells[i];
+            cell.symbol.clear();
+            cell.symbol.push_str(symbol);
+            cell.style = style;
+        }
+    }
+
+    /// Writes `text` starting at `x`, `y`, laid out like a line of the buffer (see `layout`), so
+    /// wide characters take two cells and control characters are shown as escapes. Whatever
+    /// doesn't fit before the edge of the frame is cut off. Returns the column after the last cell
+    /// written.
+    pub fn put_str(&mut self, x: usize, y: usize, text: &str, style: ContentStyle) -> usize {
+        let mut end = x;
+        // tabs are only one space wide, there are no tab stops to line up with outside the buffer
+        for glyph in layout::glyphs(text, 1) {
+            if x + glyph.col + glyph.width > self.width {
+                break;
+            }
+            for i in 0..glyph.width {
+                self.set(x + glyph.col + i, y, glyph.cell(i), style);
+            }
+            end = x + glyph.col + glyph.width;
+        }
+
+        end
+    }
+
+    fn cell(&self, x: usize, y: usize) -> &Cell {
+        &self.cells[y * self.width + x]
+    }
+}
+
+/// Keeps track of what's on the terminal and sends it the difference to the next frame.
+pub struct Renderer {
+    /// What the terminal is showing right now, or `None` if that's unknown (before the first
+    /// frame, and after the terminal is resized, which can garble it).
+    screen: Option<Frame>,
+    synchronized: bool,
+}
+
+impl Renderer {
+    pub fn new() -> Self {
+        Self {
+            screen: None,
+            synchronized: supports_synchronized_update(),
+        }
+    }
+
+    /// Forgets what's on the terminal, so the next frame is drawn from scratch.
+    pub fn invalidate(&mut self) {
+        self.screen = None;
+    }
+
+    /// Puts `frame` on the terminal, sending only what changed since the last one.
+    pub fn draw(&mut self, out: &mut impl Write, frame: Frame) -> io::Result<()> {
+        // a terminal that supports synchronized updates holds on to everything until the end of
+        // the update and then shows it at once, so a half-drawn frame is never visible
+        if self.synchronized {
+            queue!(out, terminal::BeginSynchronizedUpdate)?;
+        }
+        queue!(out, cursor::Hide)?;
+
+        let screen = match self.screen.take() {
+            Some(screen) if screen.width == frame.width && screen.height == frame.height => screen,
+            _ => {
+                // start over from a blank terminal, which is what a blank frame looks like
+                queue!(
+                    out,
+                    SetAttribute(Attribute::Reset),
+                    terminal::Clear(terminal::ClearType::All)
+                )?;
+                Frame::new(frame.width, frame.height)
+            }
+        };
+
+        // where the terminal's cursor is after the last print, to skip moving it when the next
+        // changed cell comes right after
+        let mut position = None;
+        let mut style = None;
+        for y in 0..frame.height {
+            for x in 0..frame.width {
+                let cell = frame.cell(x, y);
+                // the right halves of wide characters get drawn along with their left halves
+                if cell == screen.cell(x, y) || cell.symbol.is_empty() {
+                    continue;
+                }
+
+                if position != Some((x, y)) {
+                    queue!(out, cursor::MoveTo(x as u16, y as u16))?;
+                }
+                if style != Some(cell.style) {
+                    queue!(out, SetAttribute(Attribute::Reset), SetStyle(cell.style))?;
+                    style = Some(cell.style);
+                }
+                queue!(out, Print(&cell.symbol))?;
+
+                let wide = x + 1 < frame.width && frame.cell(x + 1, y).symbol.is_empty();
+                position = Some((x + if wide { 2 } else { 1 }, y));
+            }
+        }
+
+        queue!(out, SetAttribute(Attribute::Reset))?;
+        if let Some((x, y)) = frame.cursor {
+            queue!(out, cursor::MoveTo(x as u16, y as u16), cursor::Show)?;
+        }
+        if self.synchronized {
+            queue!(out, terminal::EndSynchronizedUpdate)?;
+        }
+        out.flush()?;
+
+        self.screen = Some(frame);
+        Ok(())
+    }
+}
+
+/// Whether the terminal understands the synchronized update mode (DEC mode 2026). Asking the
+/// terminal would mean reading its reply from the same stream as key presses, so this goes by the
+/// terminals that are known to support it.
+fn supports_synchronized_update() -> bool {
+    let term = env::var("TERM").unwrap_or_default();
+    let program = env::var("TERM_PROGRAM").unwrap_or_default();
+
+    ["kitty", "foot", "alacritty", "contour", "ghostty"]
+        .iter()
+        .any(|name| term.contains(name))
+        || ["WezTerm", "iTerm.app", "contour", "ghostty"].contains(&program.as_str())
+        || (program == "tmux" && tmux_supports_synchronized_update())
+}
+
+/// tmux passes synchronized updates through from version 3.4 on.
+fn tmux_supports_synchronized_update() -> bool {
+    let version = env::var("TERM_PROGRAM_VERSION").unwrap_or_default();
+    let mut parts = version.split('.').map(|part| {
+        part.trim_end_matches(|c: char| !c.is_ascii_digit())
+            .parse::<u32>()
+            .unwrap_or(0)
+    });
+    let major = parts.next().unwrap_or(0);
+    let minor = parts.next().unwrap_or(0);
+
+    (major, minor) >= (3, 4)
+}
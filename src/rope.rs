@@ -0,0 +1,659 @@
+//! A rope: the text is kept as a balanced binary tree of small string chunks instead of one big
+//! `String`. Every branch remembers how many bytes and newlines are below it, so finding a line,
+//! inserting and deleting only ever walk one path down from the root. That keeps them O(log n)
+//! whether the file is a few lines or a 100 MB log.
+//!
+//! The tree is kept balanced like an AVL tree: the heights of the two children of a branch never
+//! differ by more than one. All the restructuring goes through `join`, which glues two balanced
+//! trees together into a balanced tree.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// Leaves are never longer than this many bytes. Small enough that editing inside a leaf is cheap,
+/// big enough that the tree stays shallow.
+const MAX_LEAF: usize = 1024;
+
+enum Node {
+    Leaf(String),
+    Branch {
+        left: Box<Node>,
+        right: Box<Node>,
+        /// Total bytes below this branch.
+        len: usize,
+        /// Total newlines below this branch.
+        newlines: usize,
+        height: u8,
+    },
+}
+
+impl Node {
+    fn len(&self) -> usize {
+        match self {
+            Node::Leaf(s) => s.len(),
+            Node::Branch { len, .. } => *len,
+        }
+    }
+
+    fn newlines(&self) -> usize {
+        match self {
+            Node::Leaf(s) => count_newlines(s),
+            Node::Branch { newlines, .. } => *newlines,
+        }
+    }
+
+    fn height(&self) -> u8 {
+        match self {
+            Node::Leaf(_) => 0,
+            Node::Branch { height, .. } => *height,
+        }
+    }
+
+    fn branch(left: Box<Node>, right: Box<Node>) -> Box<Node> {
+        Box::new(Node::Branch {
+            len: left.len() + right.len(),
+            newlines: left.newlines() + right.newlines(),
+            height: left.height().max(right.height()) + 1,
+            left,
+            right,
+        })
+    }
+
+    /// Builds a balanced tree out of `text`, cutting it into leaves on char boundaries.
+    fn build(text: &str) -> Box<Node> {
+        let mut leaves = Vec::with_capacity(text.len() / MAX_LEAF + 1);
+        let mut rest = text;
+        while rest.len() > MAX_LEAF {
+            let mut cut = MAX_LEAF;
+            while !rest.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            leaves.push(Node::Leaf(rest[..cut].to_string()));
+            rest = &rest[cut..];
+        }
+        leaves.push(Node::Leaf(rest.to_string()));
+
+        Node::build_from_leaves(leaves)
+    }
+
+    /// Splitting the leaves in half at every level keeps the two sides of each branch within one
+    /// leaf of each other, so their heights never differ by more than one.
+    fn build_from_leaves(mut leaves: Vec<Node>) -> Box<Node> {
+        if leaves.len() == 1 {
+            return Box::new(leaves.pop().unwrap());
+        }
+
+        let right = leaves.split_off(leaves.len() / 2);
+        Node::branch(
+            Node::build_from_leaves(leaves),
+            Node::build_from_leaves(right),
+        )
+    }
+
+    fn into_children(self) -> (Box<Node>, Box<Node>) {
+        match self {
+            Node::Branch { left, right, .. } => (left, right),
+            Node::Leaf(_) => unreachable!("a leaf has no children"),
+        }
+    }
+
+    /// Makes a branch out of two balanced trees whose heights differ by at most two, rotating if
+    /// they differ by exactly two.
+    fn balance(left: Box<Node>, right: Box<Node>) -> Box<Node> {
+        let (hl, hr) = (left.height(), right.height());
+        if hl > hr + 1 {
+            let (ll, lr) = left.into_children();
+            if lr.height() > ll.height() {
+                let (lrl, lrr) = lr.into_children();
+                Node::branch(Node::branch(ll, lrl), Node::branch(lrr, right))
+            } else {
+                Node::branch(ll, Node::branch(lr, right))
+            }
+        } else if hr > hl + 1 {
+            let (rl, rr) = right.into_children();
+            if rl.height() > rr.height() {
+                let (rll, rlr) = rl.into_children();
+                Node::branch(Node::branch(left, rll), Node::branch(rlr, rr))
+            } else {
+                Node::branch(Node::branch(left, rl), rr)
+            }
+        } else {
+            Node::branch(left, right)
+        }
+    }
+
+    /// Concatenates two balanced trees into one balanced tree. This only walks down the spine of
+    /// the taller tree until the heights match, so it costs O(difference in height).
+    fn join(left: Box<Node>, right: Box<Node>) -> Box<Node> {
+        let (hl, hr) = (left.height(), right.height());
+        if hl > hr + 1 {
+            let (ll, lr) = left.into_children();
+            Node::balance(ll, Node::join(lr, right))
+        } else if hr > hl + 1 {
+            let (rl, rr) = right.into_children();
+            Node::balance(Node::join(left, rl), rr)
+        } else {
+            match (*left, *right) {
+                // keep deletes from leaving lots of tiny leaves around
+                (Node::Leaf(mut a), Node::Leaf(b)) if a.len() + b.len() <= MAX_LEAF => {
+                    a.push_str(&b);
+                    Box::new(Node::Leaf(a))
+                }
+                (left, right) => Node::branch(Box::new(left), Box::new(right)),
+            }
+        }
+    }
+
+    fn insert(self, at: usize, text: &str) -> Box<Node> {
+        match self {
+            Node::Leaf(mut s) => {
+                if s.len() + text.len() <= MAX_LEAF {
+                    s.insert_str(at, text);
+                    Box::new(Node::Leaf(s))
+                } else {
+                    let mut whole = String::with_capacity(s.len() + text.len());
+                    whole.push_str(&s[..at]);
+                    whole.push_str(text);
+                    whole.push_str(&s[at..]);
+                    Node::build(&whole)
+                }
+            }
+            Node::Branch { left, right, .. } => {
+                if at <= left.len() {
+                    Node::join(left.insert(at, text), right)
+                } else {
+                    let at = at - left.len();
+                    Node::join(left, right.insert(at, text))
+                }
+            }
+        }
+    }
+
+    /// The common case of an insert: the leaf has room for the text, so nothing needs to be
+    /// restructured and only the counts on the way down change. Returns false (without touching
+    /// anything) if the leaf is full.
+    fn insert_in_place(&mut self, at: usize, text: &str) -> bool {
+        match self {
+            Node::Leaf(s) => {
+                if s.len() + text.len() > MAX_LEAF {
+                    return false;
+                }
+                s.insert_str(at, text);
+                true
+            }
+            Node::Branch {
+                left,
+                right,
+                len,
+                newlines,
+                ..
+            } => {
+                let inserted = if at <= left.len() {
+                    left.insert_in_place(at, text)
+                } else {
+                    let at = at - left.len();
+                    right.insert_in_place(at, text)
+                };
+                if inserted {
+                    *len += text.len();
+                    *newlines += count_newlines(text);
+                }
+                inserted
+            }
+        }
+    }
+
+    /// Removes `range` (relative to this node) and returns what's left, if anything.
+    fn remove(self: Box<Node>, range: Range<usize>) -> Option<Box<Node>> {
+        if range.start == 0 && range.end >= self.len() {
+            return None;
+        }
+        if range.is_empty() {
+            return Some(self);
+        }
+
+        match *self {
+            Node::Leaf(mut s) => {
+                s.replace_range(range, "");
+                Some(Box::new(Node::Leaf(s)))
+            }
+            Node::Branch { left, right, .. } => {
+                let split = left.len();
+                let left = if range.start < split {
+                    left.remove(range.start..range.end.min(split))
+                } else {
+                    Some(left)
+                };
+                let right = if range.end > split {
+                    right.remove(range.start.saturating_sub(split)..range.end - split)
+                } else {
+                    Some(right)
+                };
+
+                match (left, right) {
+                    (Some(left), Some(right)) => Some(Node::join(left, right)),
+                    (left, right) => left.or(right),
+                }
+            }
+        }
+    }
+
+    /// The common case of a delete: the range sits inside one leaf and doesn't empty it. Returns
+    /// the number of newlines removed, or `None` (without touching anything) if the tree needs to
+    /// be restructured.
+    fn remove_in_place(&mut self, range: Range<usize>) -> Option<usize> {
+        match self {
+            Node::Leaf(s) => {
+                if range.len() == s.len() {
+                    return None;
+                }
+                let removed = count_newlines(&s[range.clone()]);
+                s.replace_range(range, "");
+                Some(removed)
+            }
+            Node::Branch {
+                left,
+                right,
+                len,
+                newlines,
+                ..
+            } => {
+                let split = left.len();
+                let removed = if range.end <= split {
+                    left.remove_in_place(range.clone())?
+                } else if range.start >= split {
+                    right.remove_in_place(range.start - split..range.end - split)?
+                } else {
+                    return None;
+                };
+                *len -= range.len();
+                *newlines -= removed;
+                Some(removed)
+            }
+        }
+    }
+
+    /// Appends the text in `range` (relative to this node) to `out`.
+    fn collect(&self, range: Range<usize>, out: &mut String) {
+        match self {
+            Node::Leaf(s) => out.push_str(&s[range]),
+            Node::Branch { left, right, .. } => {
+                let split = left.len();
+                if range.start < split {
+                    left.collect(range.start..range.end.min(split), out);
+                }
+                if range.end > split {
+                    right.collect(range.start.saturating_sub(split)..range.end - split, out);
+                }
+            }
+        }
+    }
+}
+
+/// Text stored as a rope. Offsets are in bytes and must fall on char boundaries.
+pub struct Rope {
+    root: Box<Node>,
+}
+
+impl Rope {
+    pub fn new(text: &str) -> Self {
+        Self {
+            root: Node::build(text),
+        }
+    }
+
+    /// Number of lines, where a trailing newline starts a new (empty) line.
+    pub fn line_count(&self) -> usize {
+        self.root.newlines() + 1
+    }
+
+    /// Byte offset at which `line` starts, or the end of the text if there is no such line.
+    pub fn line_to_byte(&self, line: usize) -> usize {
+        if line == 0 {
+            return 0;
+        }
+
+        // looking for the byte right after the `line`th newline
+        let mut line = line;
+        let mut offset = 0;
+        let mut node = &*self.root;
+        loop {
+            match node {
+                Node::Branch { left, right, .. } => {
+                    let newlines = left.newlines();
+                    if line <= newlines {
+                        node = left;
+                    } else {
+                        line -= newlines;
+                        offset += left.len();
+                        node = right;
+                    }
+                }
+                Node::Leaf(s) => {
+                    return offset
+                        + s.match_indices('\n')
+                            .nth(line - 1)
+                            .map_or(s.len(), |(i, _)| i + 1);
+                }
+            }
+        }
+    }
+
+    /// The line that the byte at `offset` belongs to.
+    pub fn byte_to_line(&self, offset: usize) -> usize {
+        let mut offset = offset;
+        let mut line = 0;
+        let mut node = &*self.root;
+        loop {
+            match node {
+                Node::Branch { left, right, .. } => {
+                    if offset < left.len() {
+                        node = left;
+                    } else {
+                        line += left.newlines();
+                        offset -= left.len();
+                        node = right;
+                    }
+                }
+                Node::Leaf(s) => return line + count_newlines(&s[..offset.min(s.len())]),
+            }
+        }
+    }
+
+    /// The leaf that holds the byte at `offset` (or the last leaf for the end of the text) and
+    /// the offset at which that leaf starts.
+    fn leaf_at(&self, offset: usize) -> (&str, usize) {
+        let mut offset = offset;
+        let mut start = 0;
+        let mut node = &*self.root;
+        loop {
+            match node {
+                Node::Branch { left, right, .. } => {
+                    if offset < left.len() {
+                        node = left;
+                    } else {
+                        start += left.len();
+                        offset -= left.len();
+                        node = right;
+                    }
+                }
+                Node::Leaf(s) => return (s, start),
+            }
+        }
+    }
+
+    /// The text in `range`. Only allocates if the range spans more than one leaf.
+    pub fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        let (leaf, start) = self.leaf_at(range.start);
+        if range.end <= start + leaf.len() {
+            return Cow::Borrowed(&leaf[range.start - start..range.end - start]);
+        }
+
+        let mut out = String::with_capacity(range.len());
+        self.root.collect(range, &mut out);
+        Cow::Owned(out)
+    }
+
+    /// The character that starts at `offset`, if any.
+    pub fn char_at(&self, offset: usize) -> Option<char> {
+        let (leaf, start) = self.leaf_at(offset);
+        leaf[offset - start..].chars().next()
+    }
+
+    /// The character that ends at `offset`, if any.
+    pub fn char_before(&self, offset: usize) -> Option<char> {
+        if offset == 0 {
+            return None;
+        }
+        // leaves are cut on char boundaries, so the whole char is in the leaf with its last byte
+        let (leaf, start) = self.leaf_at(offset - 1);
+        leaf[..offset - start].chars().next_back()
+    }
+
+    /// The closest offset at or before `offset` that isn't in the middle of a character.
+    pub fn floor_char_boundary(&self, offset: usize) -> usize {
+        let (leaf, start) = self.leaf_at(offset);
+        let mut i = (offset - start).min(leaf.len());
+        while !leaf.is_char_boundary(i) {
+            i -= 1;
+        }
+        start + i
+    }
+
+    pub fn insert(&mut self, at: usize, text: &str) {
+        if text.is_empty() || self.root.insert_in_place(at, text) {
+            return;
+        }
+
+        let root = std::mem::replace(&mut self.root, Box::new(Node::Leaf(String::new())));
+        self.root = root.insert(at, text);
+    }
+
+    pub fn remove(&mut self, range: Range<usize>) {
+        if range.is_empty() || self.root.remove_in_place(range.clone()).is_some() {
+            return;
+        }
+
+        let root = std::mem::replace(&mut self.root, Box::new(Node::Leaf(String::new())));
+        if let Some(root) = root.remove(range) {
+            self.root = root;
+        }
+    }
+
+    /// The text, one leaf at a time, in order.
+    pub fn chunks(&self) -> Chunks<'_> {
+        Chunks {
+            stack: vec![&self.root],
+        }
+    }
+}
+
+pub struct Chunks<'a> {
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        while let Some(node) = self.stack.pop() {
+            match node {
+                Node::Leaf(s) => return Some(s),
+                Node::Branch { left, right, .. } => {
+                    self.stack.push(right);
+                    self.stack.push(left);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+fn count_newlines(s: &str) -> usize {
+    s.bytes().filter(|&b| b == b'\n').count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Random;
+
+    /// Checks that every branch's counts add up, that the tree is balanced, and that leaves are
+    /// within size. Returns the height.
+    fn check(node: &Node) -> u8 {
+        match node {
+            Node::Leaf(s) => {
+                assert!(s.len() <= MAX_LEAF, "leaf of {} bytes", s.len());
+                0
+            }
+            Node::Branch {
+                left,
+                right,
+                len,
+                newlines,
+                height,
+            } => {
+                assert!(left.len() > 0 && right.len() > 0, "empty leaf in a branch");
+                let (hl, hr) = (check(left), check(right));
+                assert!(
+                    hl.abs_diff(hr) <= 1,
+                    "heights {hl} and {hr} under one branch"
+                );
+                assert_eq!(*len, left.len() + right.len());
+                assert_eq!(*newlines, left.newlines() + right.newlines());
+                assert_eq!(*height, hl.max(hr) + 1);
+                *height
+            }
+        }
+    }
+
+    /// Checks `rope` against `model`, the same text in a plain string.
+    fn check_rope(rope: &Rope, model: &str) {
+        check(&rope.root);
+        assert_eq!(rope.chunks().collect::<String>(), model);
+        assert_eq!(rope.line_count(), model.matches('\n').count() + 1);
+
+        let mut start = 0;
+        for (line, text) in model.split('\n').enumerate() {
+            assert_eq!(rope.line_to_byte(line), start, "start of line {line}");
+            assert_eq!(rope.byte_to_line(start), line);
+            assert_eq!(rope.byte_to_line(start + text.len()), line);
+            start += text.len() + 1;
+        }
+        assert_eq!(rope.line_to_byte(rope.line_count()), model.len());
+    }
+
+    #[test]
+    fn empty() {
+        let rope = Rope::new("");
+        check_rope(&rope, "");
+        assert_eq!(rope.char_at(0), None);
+        assert_eq!(rope.char_before(0), None);
+    }
+
+    #[test]
+    fn lines() {
+        let text = "one\ntwo\n\nfour\n";
+        let rope = Rope::new(text);
+        check_rope(&rope, text);
+        assert_eq!(rope.line_count(), 5);
+        assert_eq!(rope.line_to_byte(3), 9);
+        assert_eq!(rope.line_to_byte(4), text.len());
+        assert_eq!(rope.byte_to_line(3), 0);
+        assert_eq!(rope.byte_to_line(4), 1);
+    }
+
+    #[test]
+    fn build_cuts_leaves_on_char_boundaries() {
+        let text = "日本語\n".repeat(1000);
+        let rope = Rope::new(&text);
+        check_rope(&rope, &text);
+        assert!(rope.root.height() > 1);
+        for leaf in rope.chunks() {
+            assert!(!leaf.is_empty());
+        }
+    }
+
+    #[test]
+    fn chars_across_leaves() {
+        let text = "é".repeat(MAX_LEAF);
+        let rope = Rope::new(&text);
+        for offset in (0..text.len()).step_by(2) {
+            assert_eq!(rope.char_at(offset), Some('é'));
+            assert_eq!(rope.char_before(offset + 2), Some('é'));
+            assert_eq!(rope.floor_char_boundary(offset + 1), offset);
+        }
+        assert_eq!(rope.char_at(text.len()), None);
+    }
+
+    #[test]
+    fn slice() {
+        let text = "abc\n".repeat(1000);
+        let rope = Rope::new(&text);
+        assert!(matches!(rope.slice(4..7), Cow::Borrowed("abc")));
+        assert_eq!(rope.slice(1000..3000), &text[1000..3000]);
+        assert_eq!(rope.slice(0..text.len()), text);
+        assert_eq!(rope.slice(8..8), "");
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut rope = Rope::new("hello world");
+        rope.insert(5, ",");
+        rope.insert(12, "\nagain");
+        check_rope(&rope, "hello, world\nagain");
+        rope.remove(5..7);
+        check_rope(&rope, "helloworld\nagain");
+        rope.remove(0..rope.root.len());
+        check_rope(&rope, "");
+        rope.insert(0, "back\n");
+        check_rope(&rope, "back\n");
+    }
+
+    #[test]
+    fn insert_more_than_a_leaf() {
+        let mut rope = Rope::new("start end");
+        let big = "line\n".repeat(MAX_LEAF);
+        rope.insert(6, &big);
+        check_rope(&rope, &format!("start {big}end"));
+    }
+
+    #[test]
+    fn remove_across_leaves() {
+        let text = "0123456789\n".repeat(500);
+        let mut rope = Rope::new(&text);
+        let mut model = text.clone();
+        rope.remove(100..4000);
+        model.replace_range(100..4000, "");
+        check_rope(&rope, &model);
+    }
+
+    #[test]
+    fn stays_balanced_after_many_edits() {
+        const PIECES: [&str; 6] = ["a", "é\n", "日本", "\n", "🎉 x", "some words\n"];
+
+        let mut random = Random(0x2545_f491_4f6c_dd1d);
+        let mut model = String::new();
+        let mut rope = Rope::new("");
+        for i in 0..5000 {
+            // mostly inserts so the text grows, with the odd big one to make leaves split
+            if random.below(3) > 0 || model.is_empty() {
+                let piece = PIECES[random.below(PIECES.len())];
+                let piece = if random.below(50) == 0 {
+                    piece.repeat(300)
+                } else {
+                    piece.to_string()
+                };
+                let at = random.offset(&model);
+                rope.insert(at, &piece);
+                model.insert_str(at, &piece);
+            } else {
+                let a = random.offset(&model);
+                let b = if random.below(50) == 0 {
+                    random.offset(&model)
+                } else {
+                    let mut b = (a + random.below(8)).min(model.len());
+                    while !model.is_char_boundary(b) {
+                        b += 1;
+                    }
+                    b
+                };
+                let range = a.min(b)..a.max(b);
+                rope.remove(range.clone());
+                model.replace_range(range, "");
+            }
+
+            if i % 500 == 0 {
+                check_rope(&rope, &model);
+            } else {
+                check(&rope.root);
+            }
+        }
+        check_rope(&rope, &model);
+
+        // and the tree stays shallow for its size
+        let leaves = rope.chunks().count() as f64;
+        assert!(f64::from(rope.root.height()) <= 1.45 * (leaves + 2.0).log2());
+    }
+}
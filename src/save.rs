@@ -0,0 +1,337 @@
+//! Writing a buffer out to its file without ever leaving a half-written file behind.
+//!
+//! The text goes to a temporary file next to the real one first, which is synced to disk and then
+//! renamed over it, so a crash or a full disk leaves either the old file or the new one. The
+//! temporary file gets the old file's permissions and owner before it takes its place.
+//!
+//! Renaming replaces the directory entry though, which isn't always what we want: a symlink would
+//! be turned into a regular file, and a file with other hard links would be split off from them.
+//! Symlinks are followed so the file they point to is the one that gets replaced, and files with
+//! more than one link (or ones whose owner we can't give to the new file) are written in place
+//! instead, after a full copy has been written next to them in case that goes wrong halfway. When
+//! nothing can be written next to the file at all, the old file is copied to the recovery
+//! directory before it's written in place, so there's always one whole version of it somewhere.
+
+use std::fs::{self, File, Metadata, OpenOptions};
+use std::io::{self, Write};
+use std::path::{self, Path, PathBuf};
+use std::process;
+
+use crate::dirs;
+use crate::recovery;
+
+/// How many symlinks in a row get followed before giving up, like the kernel's limit.
+const MAX_LINKS: usize = 40;
+
+/// Writes `bytes` to the file at `path`, replacing what's there.
+pub fn write_file(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let target = resolve_symlinks(path)?;
+    let metadata = match fs::metadata(&target) {
+        Ok(metadata) => Some(metadata),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e),
+    };
+
+    let tmp = tmp_path(&target);
+    // if there's no writing next to the file (say the directory is read-only but the file isn't),
+    // writing in place is all that's left
+    let written = write_new(&tmp, bytes, metadata.as_ref());
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp);
+        return match metadata {
+            Some(_) if e.kind() == io::ErrorKind::PermissionDenied => {
+                write_in_place_with_backup(&target, bytes)
+            }
+            _ => Err(e),
+        };
+    }
+
+    let replace = match metadata {
+        Some(ref metadata) => !is_hard_linked(metadata) && copy_metadata(&tmp, metadata).is_ok(),
+        None => true,
+    };
+    if !replace {
+        // the copy next to it stays around until the file itself has been written, so a crash or
+        // error in the middle of that still leaves the new text somewhere
+        write_in_place(&target, bytes)?;
+        return fs::remove_file(&tmp);
+    }
+
+    if let Err(e) = fs::rename(&tmp, &target) {
+        let _ = fs::remove_file(&tmp);
+        return Err(e);
+    }
+    sync_dir(&target);
+
+    Ok(())
+}
+
+/// Whether the file at `path` can be written to by us.
+#[cfg(unix)]
+pub fn is_writable(path: &Path) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    // SAFETY: `path` is a valid nul-terminated string for the length of the call
+    unsafe { libc::access(path.as_ptr(), libc::W_OK) == 0 }
+}
+
+#[cfg(not(unix))]
+pub fn is_writable(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| !metadata.permissions().readonly())
+}
+
+/// Follows `path` through any symlinks to the file they end up at, which doesn't have to exist
+/// yet.
+fn resolve_symlinks(path: &Path) -> io::Result<PathBuf> {
+    let mut path = path.to_path_buf();
+    for _ in 0..MAX_LINKS {
+        match fs::symlink_metadata(&path) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                let link = fs::read_link(&path)?;
+                // relative links are relative to the directory the link is in
+                path = match path.parent() {
+                    Some(dir) => dir.join(link),
+                    None => link,
+                };
+            }
+            Ok(_) => return Ok(path),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(path),
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(io::Error::other(format!(
+        "too many levels of symbolic links at {}",
+        path.display()
+    )))
+}
+
+/// A name for the temporary file next to `target`, hidden and unlikely to be taken.
+fn tmp_path(target: &Path) -> PathBuf {
+    let name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    target.with_file_name(format!(".{name}.edythe-{}.tmp", process::id()))
+}
+
+/// Writes `bytes` to a file at `path` that mustn't exist yet, and syncs it to disk. If it's going
+/// to replace a file described by `metadata`, it's created with that file's permissions.
+fn write_new(path: &Path, bytes: &[u8], metadata: Option<&Metadata>) -> io::Result<()> {
+    let mut options = File::options();
+    options.write(true).create_new(true);
+    if let Some(metadata) = metadata {
+        set_mode(&mut options, metadata);
+    }
+    let file = options.open(path)?;
+    write_bytes(file, bytes)
+}
+
+/// Overwrites the file at `path` with `bytes`, keeping the file (and all its links) as it is.
+fn write_in_place(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let file = File::options().write(true).truncate(true).open(path)?;
+    write_bytes(file, bytes)
+}
+
+/// Overwrites the file at `path` with `bytes` when there's no copy of them next to it. Once it's
+/// truncated, there's nothing left of the old text in there either, so that's copied to the
+/// recovery directory first, and the copy is only removed once the new text is written.
+fn write_in_place_with_backup(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let backup = path::absolute(path)
+        .and_then(|path| recovery::new_path(&dirs::escape_path(&path)))
+        .and_then(|backup| {
+            fs::copy(path, &backup)?;
+            File::open(&backup)?.sync_all()?;
+            Ok(backup)
+        })
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("can't write next to it or keep a copy of it first: {e}"),
+            )
+        })?;
+
+    match write_in_place(path, bytes) {
+        Ok(()) => {
+            let _ = fs::remove_file(backup);
+            Ok(())
+        }
+        Err(e) => Err(io::Error::new(
+            e.kind(),
+            format!("{e} (what was in it is in {})", backup.display()),
+        )),
+    }
+}
+
+fn write_bytes(mut file: File, bytes: &[u8]) -> io::Result<()> {
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+#[cfg(unix)]
+fn is_hard_linked(metadata: &Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink() > 1
+}
+
+#[cfg(not(unix))]
+fn is_hard_linked(_metadata: &Metadata) -> bool {
+    false
+}
+
+/// Makes files created with `options` start out with the permission bits from `metadata`, so the
+/// text of a file only its owner can read isn't readable by anyone else while it's being written.
+/// The setuid, setgid and sticky bits are left for `copy_metadata`, once the owner is right.
+#[cfg(unix)]
+fn set_mode(options: &mut OpenOptions, metadata: &Metadata) {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+    options.mode(metadata.permissions().mode() & 0o777);
+}
+
+#[cfg(not(unix))]
+fn set_mode(_options: &mut OpenOptions, _metadata: &Metadata) {}
+
+/// Gives the file at `path` the permissions and owner from `metadata`.
+#[cfg(unix)]
+fn copy_metadata(path: &Path, metadata: &Metadata) -> io::Result<()> {
+    use std::os::unix::fs::{chown, MetadataExt};
+
+    // ownership first, since changing it can clear the setuid and setgid bits
+    let owned = fs::metadata(path)?;
+    if (owned.uid(), owned.gid()) != (metadata.uid(), metadata.gid()) {
+        chown(path, Some(metadata.uid()), Some(metadata.gid()))?;
+    }
+    fs::set_permissions(path, metadata.permissions())
+}
+
+#[cfg(not(unix))]
+fn copy_metadata(path: &Path, metadata: &Metadata) -> io::Result<()> {
+    fs::set_permissions(path, metadata.permissions())
+}
+
+/// Syncs the directory `file` is in, so the rename itself survives a crash. Not every filesystem
+/// lets directories be synced, and the file is already written either way, so failing is fine.
+fn sync_dir(file: &Path) {
+    let dir = match file.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    if let Ok(dir) = File::open(dir) {
+        let _ = dir.sync_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::os::unix::fs::{symlink, MetadataExt, PermissionsExt};
+
+    use super::*;
+
+    /// A directory of its own for each test to save files in, removed when it's dropped.
+    struct Dir(PathBuf);
+
+    impl Dir {
+        fn new(name: &str) -> Self {
+            let dir = env::temp_dir().join(format!("edythe-save-{}-{name}", process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            Dir(dir)
+        }
+
+        fn files(&self) -> Vec<String> {
+            let mut names: Vec<String> = fs::read_dir(&self.0)
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+                .collect();
+            names.sort();
+            names
+        }
+    }
+
+    impl Drop for Dir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn new_file() {
+        let dir = Dir::new("new-file");
+        let path = dir.0.join("file");
+        write_file(&path, b"text").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"text");
+        write_file(&path, b"more text").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"more text");
+        // and the temporary file is gone
+        assert_eq!(dir.files(), ["file"]);
+    }
+
+    #[test]
+    fn symlinks_are_followed() {
+        let dir = Dir::new("symlinks");
+        let file = dir.0.join("file");
+        fs::write(&file, "old").unwrap();
+        // a relative link to it, and an absolute link to that
+        symlink("file", dir.0.join("link")).unwrap();
+        symlink(dir.0.join("link"), dir.0.join("link-to-link")).unwrap();
+
+        write_file(&dir.0.join("link-to-link"), b"new").unwrap();
+        assert_eq!(fs::read(&file).unwrap(), b"new");
+        for link in ["link", "link-to-link"] {
+            let metadata = fs::symlink_metadata(dir.0.join(link)).unwrap();
+            assert!(metadata.file_type().is_symlink(), "{link} is still a link");
+        }
+        assert_eq!(dir.files(), ["file", "link", "link-to-link"]);
+
+        // one that points at nothing yet makes the file it points at
+        symlink("later", dir.0.join("dangling")).unwrap();
+        write_file(&dir.0.join("dangling"), b"made").unwrap();
+        assert_eq!(fs::read(dir.0.join("later")).unwrap(), b"made");
+
+        // and one that goes round in circles is an error rather than a hang
+        symlink("round", dir.0.join("about")).unwrap();
+        symlink("about", dir.0.join("round")).unwrap();
+        assert!(write_file(&dir.0.join("round"), b"text").is_err());
+    }
+
+    #[test]
+    fn hard_links_are_kept() {
+        let dir = Dir::new("hard-links");
+        let file = dir.0.join("file");
+        let other = dir.0.join("other");
+        fs::write(&file, "old text that's longer").unwrap();
+        fs::hard_link(&file, &other).unwrap();
+        let inode = fs::metadata(&file).unwrap().ino();
+
+        write_file(&file, b"new").unwrap();
+        // written in place, so both names still have the same file, with the new text only
+        assert_eq!(fs::read(&other).unwrap(), b"new");
+        let metadata = fs::metadata(&file).unwrap();
+        assert_eq!(metadata.ino(), inode);
+        assert_eq!(metadata.nlink(), 2);
+        assert_eq!(dir.files(), ["file", "other"]);
+    }
+
+    #[test]
+    fn mode_is_kept() {
+        let dir = Dir::new("mode");
+        let file = dir.0.join("file");
+        let linked = dir.0.join("linked");
+        for path in [&file, &linked] {
+            fs::write(path, "old").unwrap();
+            fs::set_permissions(path, fs::Permissions::from_mode(0o751)).unwrap();
+        }
+        fs::hard_link(&linked, dir.0.join("other")).unwrap();
+
+        // whether the file's replaced or written in place
+        for path in [&file, &linked] {
+            write_file(path, b"new").unwrap();
+            let mode = fs::metadata(path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o7777, 0o751, "{}", path.display());
+        }
+    }
+}
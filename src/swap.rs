@@ -0,0 +1,358 @@
+//! Swap files. While a buffer has unsaved changes, its text is written to a swap file in the state
+//! directory every so often, vim style. If the editor dies in a way that even the emergency copies
+//! can't handle (power cut, SIGKILL), the swap file is still there the next time the file is
+//! opened, and the changes can be recovered from it.
+//!
+//! Writing a big buffer out and waiting for the disk to sync it can take a while, so it's done on
+//! a thread of its own (see `Writer`) instead of in between key presses.
+
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+use std::iter;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::SystemTime;
+
+use crate::dirs;
+
+/// Identifies a swap file and the version of its format.
+const MAGIC: &[u8; 8] = b"edyswap1";
+
+/// What's in a swap file.
+pub struct Swap {
+    /// The process that wrote it.
+    pub pid: u32,
+    /// When it was written.
+    pub modified: SystemTime,
+    pub text: String,
+}
+
+/// The directory swap files go in.
+fn dir() -> Option<PathBuf> {
+    Some(dirs::state_dir()?.join("swap"))
+}
+
+/// Where the swap file for a buffer goes, `name` being one that's unique to the buffer.
+pub fn path(name: &str) -> Option<PathBuf> {
+    Some(dir()?.join(name))
+}
+
+/// Writes `text` to the swap file at `path`.
+fn write(path: &Path, text: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    // like with the undo history, a crash while writing mustn't take the last good swap with it
+    let mut tmp = path.to_path_buf().into_os_string();
+    tmp.push(".tmp");
+    let mut out = BufWriter::new(File::create(&tmp)?);
+    out.write_all(MAGIC)?;
+    out.write_all(&u64::from(process::id()).to_le_bytes())?;
+    out.write_all(text.as_bytes())?;
+    out.into_inner()?.sync_all()?;
+
+    fs::rename(tmp, path)
+}
+
+/// Reads the swap file at `path`, if there is one.
+pub fn read(path: &Path) -> io::Result<Option<Swap>> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let modified = file.metadata()?.modified()?;
+    let pid = read_pid(&mut file)?;
+
+    let mut text = String::new();
+    file.read_to_string(&mut text)?;
+
+    Ok(Some(Swap {
+        pid,
+        modified,
+        text,
+    }))
+}
+
+/// Reads the header of a swap file, which says which process wrote it.
+fn read_pid(file: &mut File) -> io::Result<u32> {
+    let mut magic = [0; 8];
+    let mut pid = [0; 8];
+    file.read_exact(&mut magic)?;
+    file.read_exact(&mut pid)?;
+    if &magic != MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not an edythe swap file",
+        ));
+    }
+
+    u32::try_from(u64::from_le_bytes(pid))
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "bad process id"))
+}
+
+/// Whether the swap file at `path` belongs to another editor that's still running. A buffer
+/// doesn't keep a swap file then, since two editors taking turns writing the same one would make
+/// it useless to both.
+pub fn in_use(path: &Path) -> bool {
+    File::open(path)
+        .and_then(|mut file| read_pid(&mut file))
+        .is_ok_and(is_running)
+}
+
+/// The swap files in the state directory whose names start with `prefix`.
+pub fn find(prefix: &str) -> io::Result<Vec<PathBuf>> {
+    let Some(dir) = dir() else {
+        return Ok(Vec::new());
+    };
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        // half-written ones from a crash in the middle of `write` are no good to anyone
+        if name.starts_with(prefix) && !name.ends_with(".tmp") {
+            paths.push(path);
+        }
+    }
+
+    Ok(paths)
+}
+
+/// Removes the swap file at `path`, if there is one.
+pub fn remove(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Whether the process that wrote a swap file is still running, in which case the file is open in
+/// there and the swap file is in use.
+pub fn is_running(pid: u32) -> bool {
+    if pid == process::id() {
+        return false;
+    }
+
+    // signal 0 doesn't get sent, but the process is still checked for; EPERM means it exists
+    // and belongs to someone else
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // SAFETY: kill with signal 0 has no effect besides the check
+    let result = unsafe { libc::kill(pid, 0) };
+    result == 0 || io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+enum Job {
+    Write(PathBuf, String),
+    Remove(PathBuf),
+    /// Everything before this is done; let whoever's waiting know.
+    Flush(Sender<()>),
+}
+
+impl Job {
+    fn path(&self) -> Option<&Path> {
+        match self {
+            Job::Write(path, _) | Job::Remove(path) => Some(path),
+            Job::Flush(_) => None,
+        }
+    }
+}
+
+/// Writes and removes swap files on a thread of its own, in the order they were asked for. Only
+/// the newest text for a swap file matters, so text that's still waiting when newer text for the
+/// same file comes in is skipped.
+pub struct Writer {
+    jobs: Sender<Job>,
+    failures: Receiver<(PathBuf, io::Error)>,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        let (jobs, queue) = mpsc::channel();
+        let (failed, failures) = mpsc::channel();
+        thread::spawn(move || run(queue, failed));
+
+        Self { jobs, failures }
+    }
+
+    /// Writes `text` to the swap file at `path`.
+    pub fn write(&self, path: PathBuf, text: String) {
+        let _ = self.jobs.send(Job::Write(path, text));
+    }
+
+    /// Removes the swap file at `path`, if there is one.
+    pub fn remove(&self, path: PathBuf) {
+        let _ = self.jobs.send(Job::Remove(path));
+    }
+
+    /// A write or remove that failed since the last time this was asked, if any.
+    pub fn failure(&self) -> Option<(PathBuf, io::Error)> {
+        self.failures.try_recv().ok()
+    }
+
+    /// Waits for everything asked for so far to be done, and returns the first failure that
+    /// hasn't been asked about yet.
+    pub fn flush(&self) -> io::Result<()> {
+        let (done, wait) = mpsc::channel();
+        if self.jobs.send(Job::Flush(done)).is_ok() {
+            let _ = wait.recv();
+        }
+
+        match self.failure() {
+            Some((_, e)) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The writer's thread, which runs until the `Writer` is dropped.
+fn run(queue: Receiver<Job>, failed: Sender<(PathBuf, io::Error)>) {
+    while let Ok(job) = queue.recv() {
+        let mut jobs: VecDeque<Job> = iter::once(job).chain(queue.try_iter()).collect();
+        while let Some(job) = jobs.pop_front() {
+            let (path, result) = match job {
+                Job::Write(path, _) if jobs.iter().any(|later| later.path() == Some(&path)) => {
+                    continue;
+                }
+                Job::Write(path, text) => {
+                    let result = write(&path, &text);
+                    (path, result)
+                }
+                Job::Remove(path) => {
+                    let result = remove(&path);
+                    (path, result)
+                }
+                Job::Flush(done) => {
+                    let _ = done.send(());
+                    continue;
+                }
+            };
+            if let Err(e) = result {
+                let _ = failed.send((path, e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    /// A directory of its own for each test's swap files, removed when it's dropped.
+    struct Dir(PathBuf);
+
+    impl Dir {
+        fn new(name: &str) -> Self {
+            Dir(env::temp_dir().join(format!("edythe-swap-{}-{name}", process::id())))
+        }
+    }
+
+    impl Drop for Dir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// Runs `jobs` the way the writer's thread does when they all come in before it gets to
+    /// them, and returns the failures.
+    fn run_all(jobs: Vec<Job>) -> Vec<(PathBuf, io::Error)> {
+        let (send, queue) = mpsc::channel();
+        for job in jobs {
+            send.send(job).unwrap();
+        }
+        drop(send);
+        let (failed, failures) = mpsc::channel();
+        run(queue, failed);
+
+        failures.try_iter().collect()
+    }
+
+    fn text(path: &Path) -> Option<String> {
+        read(path).unwrap().map(|swap| swap.text)
+    }
+
+    #[test]
+    fn write_and_remove() {
+        let dir = Dir::new("write-and-remove");
+        let path = dir.0.join("swap");
+        let writer = Writer::new();
+
+        writer.write(path.clone(), "one".to_string());
+        writer.write(path.clone(), "two".to_string());
+        writer.flush().unwrap();
+        let swap = read(&path).unwrap().unwrap();
+        assert_eq!(swap.text, "two");
+        assert_eq!(swap.pid, process::id());
+        // and this editor doesn't count as another one that has it open
+        assert!(!in_use(&path));
+
+        writer.remove(path.clone());
+        writer.flush().unwrap();
+        assert_eq!(text(&path), None);
+    }
+
+    #[test]
+    fn only_the_newest_text_is_written() {
+        let dir = Dir::new("newest");
+        let (a, b) = (dir.0.join("a"), dir.0.join("b"));
+        let failures = run_all(vec![
+            Job::Write(a.clone(), "a1".to_string()),
+            Job::Write(b.clone(), "b".to_string()),
+            Job::Write(a.clone(), "a2".to_string()),
+        ]);
+        assert!(failures.is_empty());
+        assert_eq!(text(&a).as_deref(), Some("a2"));
+        assert_eq!(text(&b).as_deref(), Some("b"));
+
+        // a write that's followed by a remove isn't done at all: if it were, it would have made
+        // the directory
+        let c = dir.0.join("sub").join("c");
+        let failures = run_all(vec![
+            Job::Write(c.clone(), "c".to_string()),
+            Job::Remove(c.clone()),
+        ]);
+        assert!(failures.is_empty());
+        assert!(!dir.0.join("sub").exists());
+
+        // but what comes after the remove still is
+        run_all(vec![
+            Job::Write(a.clone(), "a3".to_string()),
+            Job::Remove(a.clone()),
+            Job::Write(a.clone(), "a4".to_string()),
+        ]);
+        assert_eq!(text(&a).as_deref(), Some("a4"));
+    }
+
+    #[test]
+    fn failures() {
+        let dir = Dir::new("failures");
+        fs::create_dir_all(&dir.0).unwrap();
+        // a swap file can't go in a directory that's a file
+        let file = dir.0.join("file");
+        fs::write(&file, "").unwrap();
+        let path = file.join("swap");
+
+        let failures = run_all(vec![Job::Write(path.clone(), "text".to_string())]);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, path);
+
+        let writer = Writer::new();
+        writer.write(path.clone(), "text".to_string());
+        assert!(writer.flush().is_err());
+        // it's only told about once
+        assert!(writer.failure().is_none());
+    }
+}
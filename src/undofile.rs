@@ -0,0 +1,228 @@
+//! Undo history that outlives the editor. When a file's buffer is closed its history goes into a
+//! sidecar file in the state directory, along with a hash of the text on disk. Opening the file
+//! again brings the history back, unless the hash shows the file was changed by something else in
+//! the meantime, in which case the edits in it no longer line up with the text.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::dirs;
+use crate::history::History;
+
+/// Identifies an undo file and the version of its format.
+const MAGIC: &[u8; 8] = b"edyundo1";
+
+/// The sidecar that holds the history for `file`: the file's full path with `/` escaped, so every
+/// file gets its own and it's obvious which file it belongs to.
+fn sidecar_path(file: &Path) -> Option<PathBuf> {
+    let file = fs::canonicalize(file).ok()?;
+
+    Some(
+        dirs::state_dir()?
+            .join("undo")
+            .join(dirs::escape_path(&file)),
+    )
+}
+
+/// Saves `history` for `file`, whose contents on disk hash to `hash`.
+pub fn save(file: &Path, history: &History, hash: u64) -> io::Result<()> {
+    // a file that isn't on disk has nothing to match the history against later
+    let Some(path) = sidecar_path(file) else {
+        return Ok(());
+    };
+    write(&path, history, hash)
+}
+
+/// Writes `history` to the sidecar at `path`.
+fn write(path: &Path, history: &History, hash: u64) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    // write everything to the side first, so a crash never leaves a half-written history behind
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let mut out = BufWriter::new(File::create(&tmp)?);
+    out.write_all(MAGIC)?;
+    out.write_all(&hash.to_le_bytes())?;
+    history.write_to(&mut out)?;
+    out.into_inner()?.sync_all()?;
+
+    fs::rename(tmp, path)
+}
+
+pub enum Loaded {
+    Restored(History),
+    /// There's no saved history for the file.
+    Missing,
+    /// There is a saved history but the file has been changed since, so it was ignored.
+    Stale,
+}
+
+/// Loads the saved history for `file`, whose current contents hash to `hash`.
+pub fn load(file: &Path, hash: u64) -> io::Result<Loaded> {
+    let Some(path) = sidecar_path(file) else {
+        return Ok(Loaded::Missing);
+    };
+    read(&path, hash)
+}
+
+/// Reads the history in the sidecar at `path`.
+fn read(path: &Path, hash: u64) -> io::Result<Loaded> {
+    let mut input = match File::open(path) {
+        Ok(f) => BufReader::new(f),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Loaded::Missing),
+        Err(e) => return Err(e),
+    };
+
+    let mut magic = [0; 8];
+    input.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not an undo file, or from an incompatible version",
+        ));
+    }
+
+    let mut saved_hash = [0; 8];
+    input.read_exact(&mut saved_hash)?;
+    if u64::from_le_bytes(saved_hash) != hash {
+        return Ok(Loaded::Stale);
+    }
+
+    Ok(Loaded::Restored(History::read_from(&mut input)?))
+}
+
+/// 64-bit FNV-1a over the text, which unlike std's hasher is guaranteed not to change between
+/// Rust versions.
+pub fn hash<'a>(chunks: impl Iterator<Item = &'a str>) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in chunks.flat_map(str::bytes) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use super::*;
+    use crate::history::{Edit, EditKind, Step};
+
+    /// A sidecar path of its own for each test, in a directory that's removed when it's dropped.
+    struct Sidecar(PathBuf);
+
+    impl Sidecar {
+        fn new(name: &str) -> Self {
+            let dir = env::temp_dir().join(format!("edythe-undofile-{}-{name}", process::id()));
+            Sidecar(dir.join("history"))
+        }
+    }
+
+    impl Drop for Sidecar {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(self.0.parent().unwrap());
+        }
+    }
+
+    /// A history with a branch in it: "hello" typed, undone, and "bye" typed instead.
+    fn branched() -> History {
+        let mut history = History::default();
+        history.record(Edit {
+            kind: EditKind::Insert,
+            offset: 0,
+            text: "hello".to_string(),
+            cursor: 0,
+        });
+        history.seal();
+        history.undo();
+        history.record(Edit {
+            kind: EditKind::Insert,
+            offset: 0,
+            text: "bye".to_string(),
+            cursor: 0,
+        });
+        history.mark_saved();
+        history
+    }
+
+    fn bytes(history: &History) -> Vec<u8> {
+        let mut out = Vec::new();
+        history.write_to(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn round_trip() {
+        let sidecar = Sidecar::new("round-trip");
+        let history = branched();
+        write(&sidecar.0, &history, 42).unwrap();
+
+        let Ok(Loaded::Restored(mut loaded)) = read(&sidecar.0, 42) else {
+            panic!("history wasn't restored");
+        };
+        assert_eq!(bytes(&loaded), bytes(&history));
+        assert!(loaded.is_saved());
+
+        // and it still knows both branches
+        match loaded.undo().as_slice() {
+            [Step::Revert(edits)] => assert_eq!(edits[0].text, "bye"),
+            _ => panic!("expected to undo one step"),
+        }
+        match loaded.later().as_slice() {
+            [Step::Apply(edits)] => assert_eq!(edits[0].text, "hello"),
+            _ => panic!("expected to go to the other branch"),
+        }
+    }
+
+    #[test]
+    fn missing() {
+        let sidecar = Sidecar::new("missing");
+        assert!(matches!(read(&sidecar.0, 42), Ok(Loaded::Missing)));
+    }
+
+    #[test]
+    fn hash_mismatch() {
+        let sidecar = Sidecar::new("hash-mismatch");
+        write(&sidecar.0, &branched(), 42).unwrap();
+        assert!(matches!(read(&sidecar.0, 43), Ok(Loaded::Stale)));
+    }
+
+    #[test]
+    fn truncated() {
+        let sidecar = Sidecar::new("truncated");
+        write(&sidecar.0, &branched(), 42).unwrap();
+        let whole = fs::read(&sidecar.0).unwrap();
+
+        // cut off anywhere, in the header or the history, it's an error rather than a history
+        // with bits missing
+        for len in 0..whole.len() {
+            fs::write(&sidecar.0, &whole[..len]).unwrap();
+            assert!(read(&sidecar.0, 42).is_err(), "cut off after {len} bytes");
+        }
+    }
+
+    #[test]
+    fn not_an_undo_file() {
+        let sidecar = Sidecar::new("not-an-undo-file");
+        fs::create_dir_all(sidecar.0.parent().unwrap()).unwrap();
+        fs::write(&sidecar.0, b"edyundo0 and then whatever else").unwrap();
+        let error = read(&sidecar.0, 42).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn hash_is_fnv1a() {
+        assert_eq!(hash("".split_terminator('x')), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(["a"].into_iter()), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(
+            hash(["foo", "bar"].into_iter()),
+            hash(["foobar"].into_iter())
+        );
+    }
+}
@@ -0,0 +1,331 @@
+//! Grapheme clusters and how wide they are on screen.
+//!
+//! A grapheme cluster is what a reader would call one character, even when it's made of several
+//! `char`s, like an emoji with a skin tone, a flag, or a letter with combining accents. The cursor
+//! moves over these whole and backspace deletes them whole, so they never get split into pieces
+//! that don't mean anything.
+//!
+//! This follows the extended grapheme cluster rules of UAX #29, and widths go by the East Asian
+//! width property, like `wcwidth` does. The property tables in `tables.rs` are generated by
+//! `scripts/unicode_tables.py`.
+
+mod tables;
+
+use std::cmp::Ordering;
+
+/// The grapheme cluster break property, which is what the rules go by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gcb {
+    Other,
+    Cr,
+    Lf,
+    Control,
+    Extend,
+    Zwj,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    Lv,
+    Lvt,
+    /// Not a grapheme break value of its own (these are `Other`), but emoji sequences joined with
+    /// ZWJ need to know about it.
+    Pictographic,
+}
+
+fn grapheme_break(c: char) -> Gcb {
+    // most text is mostly ASCII, which is quicker to sort out without the table
+    match c {
+        '\r' => return Gcb::Cr,
+        '\n' => return Gcb::Lf,
+        '\0'..='\x1f' | '\x7f' => return Gcb::Control,
+        ' '..='~' => return Gcb::Other,
+        _ => {}
+    }
+
+    let c = u32::from(c);
+    if (0xac00..=0xd7a3).contains(&c) {
+        return if (c - 0xac00) % 28 == 0 {
+            Gcb::Lv
+        } else {
+            Gcb::Lvt
+        };
+    }
+
+    lookup(tables::GRAPHEME_BREAK, c).unwrap_or(Gcb::Other)
+}
+
+/// Finds the value for `c` in a table of sorted, non-overlapping ranges.
+fn lookup<T: Copy>(table: &[(u32, u32, T)], c: u32) -> Option<T> {
+    table
+        .binary_search_by(|&(start, end, _)| {
+            if end < c {
+                Ordering::Less
+            } else if start > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .ok()
+        .map(|i| table[i].2)
+}
+
+/// Whether `c` is a control or format character, which are clusters of their own and don't show
+/// up as anything on a terminal (or worse, do things to it).
+pub fn is_control(c: char) -> bool {
+    grapheme_break(c) == Gcb::Control
+}
+
+/// How many cells `c` takes up on a terminal on its own.
+fn char_width(c: char) -> usize {
+    if c.is_ascii() {
+        return 1;
+    }
+    lookup(tables::WIDTH, u32::from(c)).map_or(1, usize::from)
+}
+
+/// How many cells the grapheme cluster `cluster` takes up on a terminal. That's the width of its
+/// base character, since everything after that combines with it, except that a flag or an emoji
+/// asked to be shown as one (with U+FE0F) are always two wide.
+pub fn width(cluster: &str) -> usize {
+    let mut chars = cluster.chars();
+    let Some(first) = chars.next() else {
+        return 0;
+    };
+    // most clusters are one character, which is all there is to look at
+    if chars.as_str().is_empty() && first != '\u{fe0f}' {
+        return char_width(first);
+    }
+    if grapheme_break(first) == Gcb::RegionalIndicator && chars.next().is_some() {
+        return 2;
+    }
+    if cluster.contains('\u{fe0f}') {
+        return 2;
+    }
+
+    cluster
+        .chars()
+        .map(char_width)
+        .find(|&w| w > 0)
+        .unwrap_or(0)
+}
+
+/// Whether a character with property `prev` and one with `next` after it are in the same cluster,
+/// or `None` if that depends on what came before them, which is only the case for emoji joined
+/// with ZWJ and for regional indicators.
+fn joins(prev: Gcb, next: Gcb) -> Option<bool> {
+    let joined = match (prev, next) {
+        // GB3-5: line breaks and controls stand on their own, except for \r\n
+        (Gcb::Cr, Gcb::Lf) => true,
+        (Gcb::Control | Gcb::Cr | Gcb::Lf, _) | (_, Gcb::Control | Gcb::Cr | Gcb::Lf) => false,
+        // GB6-8: Hangul syllables
+        (Gcb::L, Gcb::L | Gcb::V | Gcb::Lv | Gcb::Lvt) => true,
+        (Gcb::Lv | Gcb::V, Gcb::V | Gcb::T) => true,
+        (Gcb::Lvt | Gcb::T, Gcb::T) => true,
+        // GB9-9b: combining marks and such stick to what's before them, and prepended ones to
+        // what's after
+        (_, Gcb::Extend | Gcb::Zwj | Gcb::SpacingMark) => true,
+        (Gcb::Prepend, _) => true,
+        // GB11-13
+        (Gcb::Zwj, Gcb::Pictographic) | (Gcb::RegionalIndicator, Gcb::RegionalIndicator) => {
+            return None
+        }
+        // GB999
+        _ => false,
+    };
+
+    Some(joined)
+}
+
+/// Whether there's a cluster boundary between `prev` and `next` no matter what comes before them,
+/// so that the text can be split there and each side worked on by itself.
+pub fn is_boundary(prev: char, next: char) -> bool {
+    joins(grapheme_break(prev), grapheme_break(next)) == Some(false)
+}
+
+/// The end of the grapheme cluster that starts at `offset` in `text`, which has to be the start
+/// of one. Returns `offset` itself at the end of the text.
+pub fn next_boundary(text: &str, offset: usize) -> usize {
+    let mut chars = text[offset..].char_indices();
+    let Some((_, first)) = chars.next() else {
+        return offset;
+    };
+
+    let mut prev = grapheme_break(first);
+    // regional indicators pair up into flags, so it matters how many came before
+    let mut regional = usize::from(prev == Gcb::RegionalIndicator);
+    // whether this is an emoji followed by any number of Extend, and then maybe a ZWJ
+    let mut pictographic = prev == Gcb::Pictographic;
+
+    for (i, c) in chars {
+        let next = grapheme_break(c);
+        let joined = joins(prev, next).unwrap_or(match prev {
+            // GB11: emoji ZWJ sequences
+            Gcb::Zwj => pictographic,
+            // GB12-13: flags
+            _ => regional % 2 == 1,
+        });
+        if !joined {
+            return offset + i;
+        }
+
+        regional = if next == Gcb::RegionalIndicator {
+            regional + 1
+        } else {
+            0
+        };
+        pictographic = match next {
+            Gcb::Pictographic => true,
+            Gcb::Extend => pictographic,
+            Gcb::Zwj => pictographic && prev != Gcb::Zwj,
+            _ => false,
+        };
+        prev = next;
+    }
+
+    text.len()
+}
+
+/// The start of the grapheme cluster that ends at `offset` in `text`, which has to be the end of
+/// one. Going backwards can't be done without knowing what came before, so this works forward
+/// from the start of `text`, which has to be the start of a cluster too (like the start of a
+/// line). Returns `offset` itself at the start of the text.
+pub fn prev_boundary(text: &str, offset: usize) -> usize {
+    let mut boundary = 0;
+    loop {
+        let next = next_boundary(text, boundary);
+        if next >= offset || next == boundary {
+            return boundary;
+        }
+        boundary = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `text` split into grapheme clusters, going forward.
+    fn clusters(text: &str) -> Vec<&str> {
+        let mut clusters = Vec::new();
+        let mut start = 0;
+        while start < text.len() {
+            let end = next_boundary(text, start);
+            clusters.push(&text[start..end]);
+            start = end;
+        }
+        clusters
+    }
+
+    /// `text` split into grapheme clusters, going backward, which had better come out the same.
+    fn clusters_backward(text: &str) -> Vec<&str> {
+        let mut clusters = Vec::new();
+        let mut end = text.len();
+        while end > 0 {
+            let start = prev_boundary(text, end);
+            clusters.push(&text[start..end]);
+            end = start;
+        }
+        clusters.reverse();
+        clusters
+    }
+
+    fn check(text: &str, expected: &[&str]) {
+        assert_eq!(clusters(text), expected, "{text:?}");
+        assert_eq!(clusters_backward(text), expected, "{text:?} backward");
+    }
+
+    #[test]
+    fn emoji_sequences() {
+        let family = "👨\u{200d}👩\u{200d}👧";
+        check(family, &[family]);
+        check(&format!("{family}x{family}"), &[family, "x", family]);
+        // with a skin tone (Extend) in between
+        let waving = "👋🏽\u{200d}👩";
+        check(waving, &[waving]);
+        // ZWJ only joins emoji to an emoji before it
+        check("a\u{200d}👩", &["a\u{200d}", "👩"]);
+        check("👨\u{200d}\u{200d}👩", &["👨\u{200d}\u{200d}", "👩"]);
+        // and a trailing one stays with what's before it
+        check("👨\u{200d}", &["👨\u{200d}"]);
+    }
+
+    #[test]
+    fn flags() {
+        let (fr, de) = ("🇫🇷", "🇩🇪");
+        check(&format!("{fr}{de}"), &[fr, de]);
+        // an odd one out is a cluster of its own, after the pairs
+        check(&format!("{fr}{de}🇫"), &[fr, de, "🇫"]);
+        check(&format!("x🇫{fr}"), &["x", "🇫🇫", "🇷"]);
+        // anything else in between starts the pairing over
+        check(&format!("🇫x{fr}"), &["🇫", "x", fr]);
+
+        // starting after an odd number of them pairs up differently, which is why a boundary
+        // between two can't be known without going back to the start of the run
+        let text = format!("{fr}{de}");
+        assert_eq!(next_boundary(&text, 4), 12);
+        assert!(!is_boundary('🇫', '🇷'));
+        assert!(!is_boundary('🇷', '🇩'));
+    }
+
+    #[test]
+    fn combining_marks_and_line_breaks() {
+        check("e\u{301}\u{302}x", &["e\u{301}\u{302}", "x"]);
+        // a mark at the start has nothing to combine with
+        check("\u{301}a", &["\u{301}", "a"]);
+        check("a\r\nb", &["a", "\r\n", "b"]);
+        check("\r\r\n\n\r", &["\r", "\r\n", "\n", "\r"]);
+        // marks don't combine with line breaks
+        check("\n\u{301}", &["\n", "\u{301}"]);
+        check("\r\u{301}\n", &["\r", "\u{301}", "\n"]);
+
+        assert!(!is_boundary('\r', '\n'));
+        assert!(is_boundary('\n', '\r'));
+        assert!(!is_boundary('e', '\u{301}'));
+        assert!(is_boundary('a', 'b'));
+    }
+
+    #[test]
+    fn hangul() {
+        let (l, v, t) = ('\u{1100}', '\u{1161}', '\u{11a8}');
+        // L V T spelled out, and the same as one precomposed LV or LVT syllable
+        check(&format!("{l}{v}{t}"), &[&format!("{l}{v}{t}")]);
+        check(
+            &format!("{l}{l}\u{ac00}{t}"),
+            &[&format!("{l}{l}\u{ac00}{t}")],
+        );
+        check(&format!("\u{ac01}{t}{t}"), &[&format!("\u{ac01}{t}{t}")]);
+        // but not in other orders
+        check(&format!("{t}{l}"), &[&t.to_string(), &l.to_string()]);
+        check(&format!("\u{ac01}{v}"), &["\u{ac01}", &v.to_string()]);
+        check(&format!("{v}{l}"), &[&v.to_string(), &l.to_string()]);
+    }
+
+    #[test]
+    fn prepend_and_spacing_marks() {
+        // ARABIC NUMBER SIGN goes with what's after it
+        check("\u{600}1x", &["\u{600}1", "x"]);
+        // but not a line break
+        check("\u{600}\n", &["\u{600}", "\n"]);
+        // DEVANAGARI VOWEL SIGN I goes with the consonant before it
+        check("\u{915}\u{93f}\u{915}", &["\u{915}\u{93f}", "\u{915}"]);
+
+        assert!(!is_boundary('\u{600}', '1'));
+        assert!(!is_boundary('\u{915}', '\u{93f}'));
+    }
+
+    #[test]
+    fn widths() {
+        assert_eq!(width("a"), 1);
+        assert_eq!(width("日"), 2);
+        assert_eq!(width("e\u{301}"), 1);
+        assert_eq!(width("\u{301}"), 0);
+        assert_eq!(width("👨\u{200d}👩\u{200d}👧"), 2);
+        assert_eq!(width("🇫🇷"), 2);
+        assert_eq!(width("\u{2764}\u{fe0f}"), 2);
+        assert_eq!(width(""), 0);
+    }
+}
@@ -0,0 +1,1705 @@
+// Generated by scripts/unicode_tables.py from Unicode 14.0.0. Don't edit by hand.
+
+use super::Gcb;
+
+/// Grapheme cluster break property of every code point that isn't `Other`, by range.
+pub const GRAPHEME_BREAK: &[(u32, u32, Gcb)] = &[
+    (0x0, 0x9, Gcb::Control),
+    (0xa, 0xa, Gcb::Lf),
+    (0xb, 0xc, Gcb::Control),
+    (0xd, 0xd, Gcb::Cr),
+    (0xe, 0x1f, Gcb::Control),
+    (0x7f, 0x9f, Gcb::Control),
+    (0xa9, 0xa9, Gcb::Pictographic),
+    (0xad, 0xad, Gcb::Control),
+    (0xae, 0xae, Gcb::Pictographic),
+    (0x300, 0x36f, Gcb::Extend),
+    (0x483, 0x489, Gcb::Extend),
+    (0x591, 0x5bd, Gcb::Extend),
+    (0x5bf, 0x5bf, Gcb::Extend),
+    (0x5c1, 0x5c2, Gcb::Extend),
+    (0x5c4, 0x5c5, Gcb::Extend),
+    (0x5c7, 0x5c7, Gcb::Extend),
+    (0x600, 0x605, Gcb::Prepend),
+    (0x610, 0x61a, Gcb::Extend),
+    (0x61c, 0x61c, Gcb::Control),
+    (0x64b, 0x65f, Gcb::Extend),
+    (0x670, 0x670, Gcb::Extend),
+    (0x6d6, 0x6dc, Gcb::Extend),
+    (0x6dd, 0x6dd, Gcb::Prepend),
+    (0x6df, 0x6e4, Gcb::Extend),
+    (0x6e7, 0x6e8, Gcb::Extend),
+    (0x6ea, 0x6ed, Gcb::Extend),
+    (0x70f, 0x70f, Gcb::Prepend),
+    (0x711, 0x711, Gcb::Extend),
+    (0x730, 0x74a, Gcb::Extend),
+    (0x7a6, 0x7b0, Gcb::Extend),
+    (0x7eb, 0x7f3, Gcb::Extend),
+    (0x7fd, 0x7fd, Gcb::Extend),
+    (0x816, 0x819, Gcb::Extend),
+    (0x81b, 0x823, Gcb::Extend),
+    (0x825, 0x827, Gcb::Extend),
+    (0x829, 0x82d, Gcb::Extend),
+    (0x859, 0x85b, Gcb::Extend),
+    (0x890, 0x891, Gcb::Prepend),
+    (0x898, 0x89f, Gcb::Extend),
+    (0x8ca, 0x8e1, Gcb::Extend),
+    (0x8e2, 0x8e2, Gcb::Prepend),
+    (0x8e3, 0x902, Gcb::Extend),
+    (0x903, 0x903, Gcb::SpacingMark),
+    (0x93a, 0x93a, Gcb::Extend),
+    (0x93b, 0x93b, Gcb::SpacingMark),
+    (0x93c, 0x93c, Gcb::Extend),
+    (0x93e, 0x940, Gcb::SpacingMark),
+    (0x941, 0x948, Gcb::Extend),
+    (0x949, 0x94c, Gcb::SpacingMark),
+    (0x94d, 0x94d, Gcb::Extend),
+    (0x94e, 0x94f, Gcb::SpacingMark),
+    (0x951, 0x957, Gcb::Extend),
+    (0x962, 0x963, Gcb::Extend),
+    (0x981, 0x981, Gcb::Extend),
+    (0x982, 0x983, Gcb::SpacingMark),
+    (0x9bc, 0x9bc, Gcb::Extend),
+    (0x9be, 0x9be, Gcb::Extend),
+    (0x9bf, 0x9c0, Gcb::SpacingMark),
+    (0x9c1, 0x9c4, Gcb::Extend),
+    (0x9c7, 0x9c8, Gcb::SpacingMark),
+    (0x9cb, 0x9cc, Gcb::SpacingMark),
+    (0x9cd, 0x9cd, Gcb::Extend),
+    (0x9d7, 0x9d7, Gcb::Extend),
+    (0x9e2, 0x9e3, Gcb::Extend),
+    (0x9fe, 0x9fe, Gcb::Extend),
+    (0xa01, 0xa02, Gcb::Extend),
+    (0xa03, 0xa03, Gcb::SpacingMark),
+    (0xa3c, 0xa3c, Gcb::Extend),
+    (0xa3e, 0xa40, Gcb::SpacingMark),
+    (0xa41, 0xa42, Gcb::Extend),
+    (0xa47, 0xa48, Gcb::Extend),
+    (0xa4b, 0xa4d, Gcb::Extend),
+    (0xa51, 0xa51, Gcb::Extend),
+    (0xa70, 0xa71, Gcb::Extend),
+    (0xa75, 0xa75, Gcb::Extend),
+    (0xa81, 0xa82, Gcb::Extend),
+    (0xa83, 0xa83, Gcb::SpacingMark),
+    (0xabc, 0xabc, Gcb::Extend),
+    (0xabe, 0xac0, Gcb::SpacingMark),
+    (0xac1, 0xac5, Gcb::Extend),
+    (0xac7, 0xac8, Gcb::Extend),
+    (0xac9, 0xac9, Gcb::SpacingMark),
+    (0xacb, 0xacc, Gcb::SpacingMark),
+    (0xacd, 0xacd, Gcb::Extend),
+    (0xae2, 0xae3, Gcb::Extend),
+    (0xafa, 0xaff, Gcb::Extend),
+    (0xb01, 0xb01, Gcb::Extend),
+    (0xb02, 0xb03, Gcb::SpacingMark),
+    (0xb3c, 0xb3c, Gcb::Extend),
+    (0xb3e, 0xb3f, Gcb::Extend),
+    (0xb40, 0xb40, Gcb::SpacingMark),
+    (0xb41, 0xb44, Gcb::Extend),
+    (0xb47, 0xb48, Gcb::SpacingMark),
+    (0xb4b, 0xb4c, Gcb::SpacingMark),
+    (0xb4d, 0xb4d, Gcb::Extend),
+    (0xb55, 0xb57, Gcb::Extend),
+    (0xb62, 0xb63, Gcb::Extend),
+    (0xb82, 0xb82, Gcb::Extend),
+    (0xbbe, 0xbbe, Gcb::Extend),
+    (0xbbf, 0xbbf, Gcb::SpacingMark),
+    (0xbc0, 0xbc0, Gcb::Extend),
+    (0xbc1, 0xbc2, Gcb::SpacingMark),
+    (0xbc6, 0xbc8, Gcb::SpacingMark),
+    (0xbca, 0xbcc, Gcb::SpacingMark),
+    (0xbcd, 0xbcd, Gcb::Extend),
+    (0xbd7, 0xbd7, Gcb::Extend),
+    (0xc00, 0xc00, Gcb::Extend),
+    (0xc01, 0xc03, Gcb::SpacingMark),
+    (0xc04, 0xc04, Gcb::Extend),
+    (0xc3c, 0xc3c, Gcb::Extend),
+    (0xc3e, 0xc40, Gcb::Extend),
+    (0xc41, 0xc44, Gcb::SpacingMark),
+    (0xc46, 0xc48, Gcb::Extend),
+    (0xc4a, 0xc4d, Gcb::Extend),
+    (0xc55, 0xc56, Gcb::Extend),
+    (0xc62, 0xc63, Gcb::Extend),
+    (0xc81, 0xc81, Gcb::Extend),
+    (0xc82, 0xc83, Gcb::SpacingMark),
+    (0xcbc, 0xcbc, Gcb::Extend),
+    (0xcbe, 0xcbe, Gcb::SpacingMark),
+    (0xcbf, 0xcbf, Gcb::Extend),
+    (0xcc0, 0xcc1, Gcb::SpacingMark),
+    (0xcc2, 0xcc2, Gcb::Extend),
+    (0xcc3, 0xcc4, Gcb::SpacingMark),
+    (0xcc6, 0xcc6, Gcb::Extend),
+    (0xcc7, 0xcc8, Gcb::SpacingMark),
+    (0xcca, 0xccb, Gcb::SpacingMark),
+    (0xccc, 0xccd, Gcb::Extend),
+    (0xcd5, 0xcd6, Gcb::Extend),
+    (0xce2, 0xce3, Gcb::Extend),
+    (0xd00, 0xd01, Gcb::Extend),
+    (0xd02, 0xd03, Gcb::SpacingMark),
+    (0xd3b, 0xd3c, Gcb::Extend),
+    (0xd3e, 0xd3e, Gcb::Extend),
+    (0xd3f, 0xd40, Gcb::SpacingMark),
+    (0xd41, 0xd44, Gcb::Extend),
+    (0xd46, 0xd48, Gcb::SpacingMark),
+    (0xd4a, 0xd4c, Gcb::SpacingMark),
+    (0xd4d, 0xd4d, Gcb::Extend),
+    (0xd4e, 0xd4e, Gcb::Prepend),
+    (0xd57, 0xd57, Gcb::Extend),
+    (0xd62, 0xd63, Gcb::Extend),
+    (0xd81, 0xd81, Gcb::Extend),
+    (0xd82, 0xd83, Gcb::SpacingMark),
+    (0xdca, 0xdca, Gcb::Extend),
+    (0xdcf, 0xdcf, Gcb::Extend),
+    (0xdd0, 0xdd1, Gcb::SpacingMark),
+    (0xdd2, 0xdd4, Gcb::Extend),
+    (0xdd6, 0xdd6, Gcb::Extend),
+    (0xdd8, 0xdde, Gcb::SpacingMark),
+    (0xddf, 0xddf, Gcb::Extend),
+    (0xdf2, 0xdf3, Gcb::SpacingMark),
+    (0xe31, 0xe31, Gcb::Extend),
+    (0xe33, 0xe33, Gcb::SpacingMark),
+    (0xe34, 0xe3a, Gcb::Extend),
+    (0xe47, 0xe4e, Gcb::Extend),
+    (0xeb1, 0xeb1, Gcb::Extend),
+    (0xeb3, 0xeb3, Gcb::SpacingMark),
+    (0xeb4, 0xebc, Gcb::Extend),
+    (0xec8, 0xecd, Gcb::Extend),
+    (0xf18, 0xf19, Gcb::Extend),
+    (0xf35, 0xf35, Gcb::Extend),
+    (0xf37, 0xf37, Gcb::Extend),
+    (0xf39, 0xf39, Gcb::Extend),
+    (0xf3e, 0xf3f, Gcb::SpacingMark),
+    (0xf71, 0xf7e, Gcb::Extend),
+    (0xf7f, 0xf7f, Gcb::SpacingMark),
+    (0xf80, 0xf84, Gcb::Extend),
+    (0xf86, 0xf87, Gcb::Extend),
+    (0xf8d, 0xf97, Gcb::Extend),
+    (0xf99, 0xfbc, Gcb::Extend),
+    (0xfc6, 0xfc6, Gcb::Extend),
+    (0x102d, 0x1030, Gcb::Extend),
+    (0x1031, 0x1031, Gcb::SpacingMark),
+    (0x1032, 0x1037, Gcb::Extend),
+    (0x1039, 0x103a, Gcb::Extend),
+    (0x103b, 0x103c, Gcb::SpacingMark),
+    (0x103d, 0x103e, Gcb::Extend),
+    (0x1056, 0x1057, Gcb::SpacingMark),
+    (0x1058, 0x1059, Gcb::Extend),
+    (0x105e, 0x1060, Gcb::Extend),
+    (0x1071, 0x1074, Gcb::Extend),
+    (0x1082, 0x1082, Gcb::Extend),
+    (0x1084, 0x1084, Gcb::SpacingMark),
+    (0x1085, 0x1086, Gcb::Extend),
+    (0x108d, 0x108d, Gcb::Extend),
+    (0x109d, 0x109d, Gcb::Extend),
+    (0x1100, 0x115f, Gcb::L),
+    (0x1160, 0x11a7, Gcb::V),
+    (0x11a8, 0x11ff, Gcb::T),
+    (0x135d, 0x135f, Gcb::Extend),
+    (0x1712, 0x1714, Gcb::Extend),
+    (0x1715, 0x1715, Gcb::SpacingMark),
+    (0x1732, 0x1733, Gcb::Extend),
+    (0x1734, 0x1734, Gcb::SpacingMark),
+    (0x1752, 0x1753, Gcb::Extend),
+    (0x1772, 0x1773, Gcb::Extend),
+    (0x17b4, 0x17b5, Gcb::Extend),
+    (0x17b6, 0x17b6, Gcb::SpacingMark),
+    (0x17b7, 0x17bd, Gcb::Extend),
+    (0x17be, 0x17c5, Gcb::SpacingMark),
+    (0x17c6, 0x17c6, Gcb::Extend),
+    (0x17c7, 0x17c8, Gcb::SpacingMark),
+    (0x17c9, 0x17d3, Gcb::Extend),
+    (0x17dd, 0x17dd, Gcb::Extend),
+    (0x180b, 0x180d, Gcb::Extend),
+    (0x180e, 0x180e, Gcb::Control),
+    (0x180f, 0x180f, Gcb::Extend),
+    (0x1885, 0x1886, Gcb::Extend),
+    (0x18a9, 0x18a9, Gcb::Extend),
+    (0x1920, 0x1922, Gcb::Extend),
+    (0x1923, 0x1926, Gcb::SpacingMark),
+    (0x1927, 0x1928, Gcb::Extend),
+    (0x1929, 0x192b, Gcb::SpacingMark),
+    (0x1930, 0x1931, Gcb::SpacingMark),
+    (0x1932, 0x1932, Gcb::Extend),
+    (0x1933, 0x1938, Gcb::SpacingMark),
+    (0x1939, 0x193b, Gcb::Extend),
+    (0x1a17, 0x1a18, Gcb::Extend),
+    (0x1a19, 0x1a1a, Gcb::SpacingMark),
+    (0x1a1b, 0x1a1b, Gcb::Extend),
+    (0x1a55, 0x1a55, Gcb::SpacingMark),
+    (0x1a56, 0x1a56, Gcb::Extend),
+    (0x1a57, 0x1a57, Gcb::SpacingMark),
+    (0x1a58, 0x1a5e, Gcb::Extend),
+    (0x1a60, 0x1a60, Gcb::Extend),
+    (0x1a62, 0x1a62, Gcb::Extend),
+    (0x1a65, 0x1a6c, Gcb::Extend),
+    (0x1a6d, 0x1a72, Gcb::SpacingMark),
+    (0x1a73, 0x1a7c, Gcb::Extend),
+    (0x1a7f, 0x1a7f, Gcb::Extend),
+    (0x1ab0, 0x1ace, Gcb::Extend),
+    (0x1b00, 0x1b03, Gcb::Extend),
+    (0x1b04, 0x1b04, Gcb::SpacingMark),
+    (0x1b34, 0x1b3a, Gcb::Extend),
+    (0x1b3b, 0x1b3b, Gcb::SpacingMark),
+    (0x1b3c, 0x1b3c, Gcb::Extend),
+    (0x1b3d, 0x1b41, Gcb::SpacingMark),
+    (0x1b42, 0x1b42, Gcb::Extend),
+    (0x1b43, 0x1b44, Gcb::SpacingMark),
+    (0x1b6b, 0x1b73, Gcb::Extend),
+    (0x1b80, 0x1b81, Gcb::Extend),
+    (0x1b82, 0x1b82, Gcb::SpacingMark),
+    (0x1ba1, 0x1ba1, Gcb::SpacingMark),
+    (0x1ba2, 0x1ba5, Gcb::Extend),
+    (0x1ba6, 0x1ba7, Gcb::SpacingMark),
+    (0x1ba8, 0x1ba9, Gcb::Extend),
+    (0x1baa, 0x1baa, Gcb::SpacingMark),
+    (0x1bab, 0x1bad, Gcb::Extend),
+    (0x1be6, 0x1be6, Gcb::Extend),
+    (0x1be7, 0x1be7, Gcb::SpacingMark),
+    (0x1be8, 0x1be9, Gcb::Extend),
+    (0x1bea, 0x1bec, Gcb::SpacingMark),
+    (0x1bed, 0x1bed, Gcb::Extend),
+    (0x1bee, 0x1bee, Gcb::SpacingMark),
+    (0x1bef, 0x1bf1, Gcb::Extend),
+    (0x1bf2, 0x1bf3, Gcb::SpacingMark),
+    (0x1c24, 0x1c2b, Gcb::SpacingMark),
+    (0x1c2c, 0x1c33, Gcb::Extend),
+    (0x1c34, 0x1c35, Gcb::SpacingMark),
+    (0x1c36, 0x1c37, Gcb::Extend),
+    (0x1cd0, 0x1cd2, Gcb::Extend),
+    (0x1cd4, 0x1ce0, Gcb::Extend),
+    (0x1ce1, 0x1ce1, Gcb::SpacingMark),
+    (0x1ce2, 0x1ce8, Gcb::Extend),
+    (0x1ced, 0x1ced, Gcb::Extend),
+    (0x1cf4, 0x1cf4, Gcb::Extend),
+    (0x1cf7, 0x1cf7, Gcb::SpacingMark),
+    (0x1cf8, 0x1cf9, Gcb::Extend),
+    (0x1dc0, 0x1dff, Gcb::Extend),
+    (0x200b, 0x200b, Gcb::Control),
+    (0x200c, 0x200c, Gcb::Extend),
+    (0x200d, 0x200d, Gcb::Zwj),
+    (0x200e, 0x200f, Gcb::Control),
+    (0x2028, 0x202e, Gcb::Control),
+    (0x203c, 0x203c, Gcb::Pictographic),
+    (0x2049, 0x2049, Gcb::Pictographic),
+    (0x2060, 0x2064, Gcb::Control),
+    (0x2066, 0x206f, Gcb::Control),
+    (0x20d0, 0x20f0, Gcb::Extend),
+    (0x2122, 0x2122, Gcb::Pictographic),
+    (0x2139, 0x2139, Gcb::Pictographic),
+    (0x2194, 0x2199, Gcb::Pictographic),
+    (0x21a9, 0x21aa, Gcb::Pictographic),
+    (0x231a, 0x231b, Gcb::Pictographic),
+    (0x2328, 0x2328, Gcb::Pictographic),
+    (0x2388, 0x2388, Gcb::Pictographic),
+    (0x23cf, 0x23cf, Gcb::Pictographic),
+    (0x23e9, 0x23f3, Gcb::Pictographic),
+    (0x23f8, 0x23fa, Gcb::Pictographic),
+    (0x24c2, 0x24c2, Gcb::Pictographic),
+    (0x25aa, 0x25ab, Gcb::Pictographic),
+    (0x25b6, 0x25b6, Gcb::Pictographic),
+    (0x25c0, 0x25c0, Gcb::Pictographic),
+    (0x25fb, 0x25fe, Gcb::Pictographic),
+    (0x2600, 0x2605, Gcb::Pictographic),
+    (0x2607, 0x2612, Gcb::Pictographic),
+    (0x2614, 0x2685, Gcb::Pictographic),
+    (0x2690, 0x2705, Gcb::Pictographic),
+    (0x2708, 0x2712, Gcb::Pictographic),
+    (0x2714, 0x2714, Gcb::Pictographic),
+    (0x2716, 0x2716, Gcb::Pictographic),
+    (0x271d, 0x271d, Gcb::Pictographic),
+    (0x2721, 0x2721, Gcb::Pictographic),
+    (0x2728, 0x2728, Gcb::Pictographic),
+    (0x2733, 0x2734, Gcb::Pictographic),
+    (0x2744, 0x2744, Gcb::Pictographic),
+    (0x2747, 0x2747, Gcb::Pictographic),
+    (0x274c, 0x274c, Gcb::Pictographic),
+    (0x274e, 0x274e, Gcb::Pictographic),
+    (0x2753, 0x2755, Gcb::Pictographic),
+    (0x2757, 0x2757, Gcb::Pictographic),
+    (0x2763, 0x2767, Gcb::Pictographic),
+    (0x2795, 0x2797, Gcb::Pictographic),
+    (0x27a1, 0x27a1, Gcb::Pictographic),
+    (0x27b0, 0x27b0, Gcb::Pictographic),
+    (0x27bf, 0x27bf, Gcb::Pictographic),
+    (0x2934, 0x2935, Gcb::Pictographic),
+    (0x2b05, 0x2b07, Gcb::Pictographic),
+    (0x2b1b, 0x2b1c, Gcb::Pictographic),
+    (0x2b50, 0x2b50, Gcb::Pictographic),
+    (0x2b55, 0x2b55, Gcb::Pictographic),
+    (0x2cef, 0x2cf1, Gcb::Extend),
+    (0x2d7f, 0x2d7f, Gcb::Extend),
+    (0x2de0, 0x2dff, Gcb::Extend),
+    (0x302a, 0x302f, Gcb::Extend),
+    (0x3030, 0x3030, Gcb::Pictographic),
+    (0x303d, 0x303d, Gcb::Pictographic),
+    (0x3099, 0x309a, Gcb::Extend),
+    (0x3297, 0x3297, Gcb::Pictographic),
+    (0x3299, 0x3299, Gcb::Pictographic),
+    (0xa66f, 0xa672, Gcb::Extend),
+    (0xa674, 0xa67d, Gcb::Extend),
+    (0xa69e, 0xa69f, Gcb::Extend),
+    (0xa6f0, 0xa6f1, Gcb::Extend),
+    (0xa802, 0xa802, Gcb::Extend),
+    (0xa806, 0xa806, Gcb::Extend),
+    (0xa80b, 0xa80b, Gcb::Extend),
+    (0xa823, 0xa824, Gcb::SpacingMark),
+    (0xa825, 0xa826, Gcb::Extend),
+    (0xa827, 0xa827, Gcb::SpacingMark),
+    (0xa82c, 0xa82c, Gcb::Extend),
+    (0xa880, 0xa881, Gcb::SpacingMark),
+    (0xa8b4, 0xa8c3, Gcb::SpacingMark),
+    (0xa8c4, 0xa8c5, Gcb::Extend),
+    (0xa8e0, 0xa8f1, Gcb::Extend),
+    (0xa8ff, 0xa8ff, Gcb::Extend),
+    (0xa926, 0xa92d, Gcb::Extend),
+    (0xa947, 0xa951, Gcb::Extend),
+    (0xa952, 0xa953, Gcb::SpacingMark),
+    (0xa960, 0xa97c, Gcb::L),
+    (0xa980, 0xa982, Gcb::Extend),
+    (0xa983, 0xa983, Gcb::SpacingMark),
+    (0xa9b3, 0xa9b3, Gcb::Extend),
+    (0xa9b4, 0xa9b5, Gcb::SpacingMark),
+    (0xa9b6, 0xa9b9, Gcb::Extend),
+    (0xa9ba, 0xa9bb, Gcb::SpacingMark),
+    (0xa9bc, 0xa9bd, Gcb::Extend),
+    (0xa9be, 0xa9c0, Gcb::SpacingMark),
+    (0xa9e5, 0xa9e5, Gcb::Extend),
+    (0xaa29, 0xaa2e, Gcb::Extend),
+    (0xaa2f, 0xaa30, Gcb::SpacingMark),
+    (0xaa31, 0xaa32, Gcb::Extend),
+    (0xaa33, 0xaa34, Gcb::SpacingMark),
+    (0xaa35, 0xaa36, Gcb::Extend),
+    (0xaa43, 0xaa43, Gcb::Extend),
+    (0xaa4c, 0xaa4c, Gcb::Extend),
+    (0xaa4d, 0xaa4d, Gcb::SpacingMark),
+    (0xaa7c, 0xaa7c, Gcb::Extend),
+    (0xaab0, 0xaab0, Gcb::Extend),
+    (0xaab2, 0xaab4, Gcb::Extend),
+    (0xaab7, 0xaab8, Gcb::Extend),
+    (0xaabe, 0xaabf, Gcb::Extend),
+    (0xaac1, 0xaac1, Gcb::Extend),
+    (0xaaeb, 0xaaeb, Gcb::SpacingMark),
+    (0xaaec, 0xaaed, Gcb::Extend),
+    (0xaaee, 0xaaef, Gcb::SpacingMark),
+    (0xaaf5, 0xaaf5, Gcb::SpacingMark),
+    (0xaaf6, 0xaaf6, Gcb::Extend),
+    (0xabe3, 0xabe4, Gcb::SpacingMark),
+    (0xabe5, 0xabe5, Gcb::Extend),
+    (0xabe6, 0xabe7, Gcb::SpacingMark),
+    (0xabe8, 0xabe8, Gcb::Extend),
+    (0xabe9, 0xabea, Gcb::SpacingMark),
+    (0xabec, 0xabec, Gcb::SpacingMark),
+    (0xabed, 0xabed, Gcb::Extend),
+    (0xd7b0, 0xd7c6, Gcb::V),
+    (0xd7cb, 0xd7fb, Gcb::T),
+    (0xfb1e, 0xfb1e, Gcb::Extend),
+    (0xfe00, 0xfe0f, Gcb::Extend),
+    (0xfe20, 0xfe2f, Gcb::Extend),
+    (0xfeff, 0xfeff, Gcb::Control),
+    (0xff9e, 0xff9f, Gcb::Extend),
+    (0xfff9, 0xfffb, Gcb::Control),
+    (0x101fd, 0x101fd, Gcb::Extend),
+    (0x102e0, 0x102e0, Gcb::Extend),
+    (0x10376, 0x1037a, Gcb::Extend),
+    (0x10a01, 0x10a03, Gcb::Extend),
+    (0x10a05, 0x10a06, Gcb::Extend),
+    (0x10a0c, 0x10a0f, Gcb::Extend),
+    (0x10a38, 0x10a3a, Gcb::Extend),
+    (0x10a3f, 0x10a3f, Gcb::Extend),
+    (0x10ae5, 0x10ae6, Gcb::Extend),
+    (0x10d24, 0x10d27, Gcb::Extend),
+    (0x10eab, 0x10eac, Gcb::Extend),
+    (0x10f46, 0x10f50, Gcb::Extend),
+    (0x10f82, 0x10f85, Gcb::Extend),
+    (0x11000, 0x11000, Gcb::SpacingMark),
+    (0x11001, 0x11001, Gcb::Extend),
+    (0x11002, 0x11002, Gcb::SpacingMark),
+    (0x11038, 0x11046, Gcb::Extend),
+    (0x11070, 0x11070, Gcb::Extend),
+    (0x11073, 0x11074, Gcb::Extend),
+    (0x1107f, 0x11081, Gcb::Extend),
+    (0x11082, 0x11082, Gcb::SpacingMark),
+    (0x110b0, 0x110b2, Gcb::SpacingMark),
+    (0x110b3, 0x110b6, Gcb::Extend),
+    (0x110b7, 0x110b8, Gcb::SpacingMark),
+    (0x110b9, 0x110ba, Gcb::Extend),
+    (0x110bd, 0x110bd, Gcb::Prepend),
+    (0x110c2, 0x110c2, Gcb::Extend),
+    (0x110cd, 0x110cd, Gcb::Prepend),
+    (0x11100, 0x11102, Gcb::Extend),
+    (0x11127, 0x1112b, Gcb::Extend),
+    (0x1112c, 0x1112c, Gcb::SpacingMark),
+    (0x1112d, 0x11134, Gcb::Extend),
+    (0x11145, 0x11146, Gcb::SpacingMark),
+    (0x11173, 0x11173, Gcb::Extend),
+    (0x11180, 0x11181, Gcb::Extend),
+    (0x11182, 0x11182, Gcb::SpacingMark),
+    (0x111b3, 0x111b5, Gcb::SpacingMark),
+    (0x111b6, 0x111be, Gcb::Extend),
+    (0x111bf, 0x111c0, Gcb::SpacingMark),
+    (0x111c2, 0x111c3, Gcb::Prepend),
+    (0x111c9, 0x111cc, Gcb::Extend),
+    (0x111ce, 0x111ce, Gcb::SpacingMark),
+    (0x111cf, 0x111cf, Gcb::Extend),
+    (0x1122c, 0x1122e, Gcb::SpacingMark),
+    (0x1122f, 0x11231, Gcb::Extend),
+    (0x11232, 0x11233, Gcb::SpacingMark),
+    (0x11234, 0x11234, Gcb::Extend),
+    (0x11235, 0x11235, Gcb::SpacingMark),
+    (0x11236, 0x11237, Gcb::Extend),
+    (0x1123e, 0x1123e, Gcb::Extend),
+    (0x112df, 0x112df, Gcb::Extend),
+    (0x112e0, 0x112e2, Gcb::SpacingMark),
+    (0x112e3, 0x112ea, Gcb::Extend),
+    (0x11300, 0x11301, Gcb::Extend),
+    (0x11302, 0x11303, Gcb::SpacingMark),
+    (0x1133b, 0x1133c, Gcb::Extend),
+    (0x1133e, 0x1133e, Gcb::Extend),
+    (0x1133f, 0x1133f, Gcb::SpacingMark),
+    (0x11340, 0x11340, Gcb::Extend),
+    (0x11341, 0x11344, Gcb::SpacingMark),
+    (0x11347, 0x11348, Gcb::SpacingMark),
+    (0x1134b, 0x1134d, Gcb::SpacingMark),
+    (0x11357, 0x11357, Gcb::Extend),
+    (0x11362, 0x11363, Gcb::SpacingMark),
+    (0x11366, 0x1136c, Gcb::Extend),
+    (0x11370, 0x11374, Gcb::Extend),
+    (0x11435, 0x11437, Gcb::SpacingMark),
+    (0x11438, 0x1143f, Gcb::Extend),
+    (0x11440, 0x11441, Gcb::SpacingMark),
+    (0x11442, 0x11444, Gcb::Extend),
+    (0x11445, 0x11445, Gcb::SpacingMark),
+    (0x11446, 0x11446, Gcb::Extend),
+    (0x1145e, 0x1145e, Gcb::Extend),
+    (0x114b0, 0x114b0, Gcb::Extend),
+    (0x114b1, 0x114b2, Gcb::SpacingMark),
+    (0x114b3, 0x114b8, Gcb::Extend),
+    (0x114b9, 0x114b9, Gcb::SpacingMark),
+    (0x114ba, 0x114ba, Gcb::Extend),
+    (0x114bb, 0x114bc, Gcb::SpacingMark),
+    (0x114bd, 0x114bd, Gcb::Extend),
+    (0x114be, 0x114be, Gcb::SpacingMark),
+    (0x114bf, 0x114c0, Gcb::Extend),
+    (0x114c1, 0x114c1, Gcb::SpacingMark),
+    (0x114c2, 0x114c3, Gcb::Extend),
+    (0x115af, 0x115af, Gcb::Extend),
+    (0x115b0, 0x115b1, Gcb::SpacingMark),
+    (0x115b2, 0x115b5, Gcb::Extend),
+    (0x115b8, 0x115bb, Gcb::SpacingMark),
+    (0x115bc, 0x115bd, Gcb::Extend),
+    (0x115be, 0x115be, Gcb::SpacingMark),
+    (0x115bf, 0x115c0, Gcb::Extend),
+    (0x115dc, 0x115dd, Gcb::Extend),
+    (0x11630, 0x11632, Gcb::SpacingMark),
+    (0x11633, 0x1163a, Gcb::Extend),
+    (0x1163b, 0x1163c, Gcb::SpacingMark),
+    (0x1163d, 0x1163d, Gcb::Extend),
+    (0x1163e, 0x1163e, Gcb::SpacingMark),
+    (0x1163f, 0x11640, Gcb::Extend),
+    (0x116ab, 0x116ab, Gcb::Extend),
+    (0x116ac, 0x116ac, Gcb::SpacingMark),
+    (0x116ad, 0x116ad, Gcb::Extend),
+    (0x116ae, 0x116af, Gcb::SpacingMark),
+    (0x116b0, 0x116b5, Gcb::Extend),
+    (0x116b6, 0x116b6, Gcb::SpacingMark),
+    (0x116b7, 0x116b7, Gcb::Extend),
+    (0x1171d, 0x1171f, Gcb::Extend),
+    (0x11722, 0x11725, Gcb::Extend),
+    (0x11726, 0x11726, Gcb::SpacingMark),
+    (0x11727, 0x1172b, Gcb::Extend),
+    (0x1182c, 0x1182e, Gcb::SpacingMark),
+    (0x1182f, 0x11837, Gcb::Extend),
+    (0x11838, 0x11838, Gcb::SpacingMark),
+    (0x11839, 0x1183a, Gcb::Extend),
+    (0x11930, 0x11930, Gcb::Extend),
+    (0x11931, 0x11935, Gcb::SpacingMark),
+    (0x11937, 0x11938, Gcb::SpacingMark),
+    (0x1193b, 0x1193c, Gcb::Extend),
+    (0x1193d, 0x1193d, Gcb::SpacingMark),
+    (0x1193e, 0x1193e, Gcb::Extend),
+    (0x1193f, 0x1193f, Gcb::Prepend),
+    (0x11940, 0x11940, Gcb::SpacingMark),
+    (0x11941, 0x11941, Gcb::Prepend),
+    (0x11942, 0x11942, Gcb::SpacingMark),
+    (0x11943, 0x11943, Gcb::Extend),
+    (0x119d1, 0x119d3, Gcb::SpacingMark),
+    (0x119d4, 0x119d7, Gcb::Extend),
+    (0x119da, 0x119db, Gcb::Extend),
+    (0x119dc, 0x119df, Gcb::SpacingMark),
+    (0x119e0, 0x119e0, Gcb::Extend),
+    (0x119e4, 0x119e4, Gcb::SpacingMark),
+    (0x11a01, 0x11a0a, Gcb::Extend),
+    (0x11a33, 0x11a38, Gcb::Extend),
+    (0x11a39, 0x11a39, Gcb::SpacingMark),
+    (0x11a3a, 0x11a3a, Gcb::Prepend),
+    (0x11a3b, 0x11a3e, Gcb::Extend),
+    (0x11a47, 0x11a47, Gcb::Extend),
+    (0x11a51, 0x11a56, Gcb::Extend),
+    (0x11a57, 0x11a58, Gcb::SpacingMark),
+    (0x11a59, 0x11a5b, Gcb::Extend),
+    (0x11a84, 0x11a89, Gcb::Prepend),
+    (0x11a8a, 0x11a96, Gcb::Extend),
+    (0x11a97, 0x11a97, Gcb::SpacingMark),
+    (0x11a98, 0x11a99, Gcb::Extend),
+    (0x11c2f, 0x11c2f, Gcb::SpacingMark),
+    (0x11c30, 0x11c36, Gcb::Extend),
+    (0x11c38, 0x11c3d, Gcb::Extend),
+    (0x11c3e, 0x11c3e, Gcb::SpacingMark),
+    (0x11c3f, 0x11c3f, Gcb::Extend),
+    (0x11c92, 0x11ca7, Gcb::Extend),
+    (0x11ca9, 0x11ca9, Gcb::SpacingMark),
+    (0x11caa, 0x11cb0, Gcb::Extend),
+    (0x11cb1, 0x11cb1, Gcb::SpacingMark),
+    (0x11cb2, 0x11cb3, Gcb::Extend),
+    (0x11cb4, 0x11cb4, Gcb::SpacingMark),
+    (0x11cb5, 0x11cb6, Gcb::Extend),
+    (0x11d31, 0x11d36, Gcb::Extend),
+    (0x11d3a, 0x11d3a, Gcb::Extend),
+    (0x11d3c, 0x11d3d, Gcb::Extend),
+    (0x11d3f, 0x11d45, Gcb::Extend),
+    (0x11d46, 0x11d46, Gcb::Prepend),
+    (0x11d47, 0x11d47, Gcb::Extend),
+    (0x11d8a, 0x11d8e, Gcb::SpacingMark),
+    (0x11d90, 0x11d91, Gcb::Extend),
+    (0x11d93, 0x11d94, Gcb::SpacingMark),
+    (0x11d95, 0x11d95, Gcb::Extend),
+    (0x11d96, 0x11d96, Gcb::SpacingMark),
+    (0x11d97, 0x11d97, Gcb::Extend),
+    (0x11ef3, 0x11ef4, Gcb::Extend),
+    (0x11ef5, 0x11ef6, Gcb::SpacingMark),
+    (0x13430, 0x13438, Gcb::Control),
+    (0x16af0, 0x16af4, Gcb::Extend),
+    (0x16b30, 0x16b36, Gcb::Extend),
+    (0x16f4f, 0x16f4f, Gcb::Extend),
+    (0x16f51, 0x16f87, Gcb::SpacingMark),
+    (0x16f8f, 0x16f92, Gcb::Extend),
+    (0x16fe4, 0x16fe4, Gcb::Extend),
+    (0x16ff0, 0x16ff1, Gcb::SpacingMark),
+    (0x1bc9d, 0x1bc9e, Gcb::Extend),
+    (0x1bca0, 0x1bca3, Gcb::Control),
+    (0x1cf00, 0x1cf2d, Gcb::Extend),
+    (0x1cf30, 0x1cf46, Gcb::Extend),
+    (0x1d165, 0x1d165, Gcb::Extend),
+    (0x1d166, 0x1d166, Gcb::SpacingMark),
+    (0x1d167, 0x1d169, Gcb::Extend),
+    (0x1d16d, 0x1d16d, Gcb::SpacingMark),
+    (0x1d16e, 0x1d172, Gcb::Extend),
+    (0x1d173, 0x1d17a, Gcb::Control),
+    (0x1d17b, 0x1d182, Gcb::Extend),
+    (0x1d185, 0x1d18b, Gcb::Extend),
+    (0x1d1aa, 0x1d1ad, Gcb::Extend),
+    (0x1d242, 0x1d244, Gcb::Extend),
+    (0x1da00, 0x1da36, Gcb::Extend),
+    (0x1da3b, 0x1da6c, Gcb::Extend),
+    (0x1da75, 0x1da75, Gcb::Extend),
+    (0x1da84, 0x1da84, Gcb::Extend),
+    (0x1da9b, 0x1da9f, Gcb::Extend),
+    (0x1daa1, 0x1daaf, Gcb::Extend),
+    (0x1e000, 0x1e006, Gcb::Extend),
+    (0x1e008, 0x1e018, Gcb::Extend),
+    (0x1e01b, 0x1e021, Gcb::Extend),
+    (0x1e023, 0x1e024, Gcb::Extend),
+    (0x1e026, 0x1e02a, Gcb::Extend),
+    (0x1e130, 0x1e136, Gcb::Extend),
+    (0x1e2ae, 0x1e2ae, Gcb::Extend),
+    (0x1e2ec, 0x1e2ef, Gcb::Extend),
+    (0x1e8d0, 0x1e8d6, Gcb::Extend),
+    (0x1e944, 0x1e94a, Gcb::Extend),
+    (0x1f000, 0x1f0ff, Gcb::Pictographic),
+    (0x1f10d, 0x1f10f, Gcb::Pictographic),
+    (0x1f12f, 0x1f12f, Gcb::Pictographic),
+    (0x1f16c, 0x1f171, Gcb::Pictographic),
+    (0x1f17e, 0x1f17f, Gcb::Pictographic),
+    (0x1f18e, 0x1f18e, Gcb::Pictographic),
+    (0x1f191, 0x1f19a, Gcb::Pictographic),
+    (0x1f1ad, 0x1f1e5, Gcb::Pictographic),
+    (0x1f1e6, 0x1f1ff, Gcb::RegionalIndicator),
+    (0x1f201, 0x1f20f, Gcb::Pictographic),
+    (0x1f21a, 0x1f21a, Gcb::Pictographic),
+    (0x1f22f, 0x1f22f, Gcb::Pictographic),
+    (0x1f232, 0x1f23a, Gcb::Pictographic),
+    (0x1f23c, 0x1f23f, Gcb::Pictographic),
+    (0x1f249, 0x1f3fa, Gcb::Pictographic),
+    (0x1f3fb, 0x1f3ff, Gcb::Extend),
+    (0x1f400, 0x1f53d, Gcb::Pictographic),
+    (0x1f546, 0x1f64f, Gcb::Pictographic),
+    (0x1f680, 0x1f6ff, Gcb::Pictographic),
+    (0x1f774, 0x1f77f, Gcb::Pictographic),
+    (0x1f7d5, 0x1f7ff, Gcb::Pictographic),
+    (0x1f80c, 0x1f80f, Gcb::Pictographic),
+    (0x1f848, 0x1f84f, Gcb::Pictographic),
+    (0x1f85a, 0x1f85f, Gcb::Pictographic),
+    (0x1f888, 0x1f88f, Gcb::Pictographic),
+    (0x1f8ae, 0x1f8ff, Gcb::Pictographic),
+    (0x1f90c, 0x1f93a, Gcb::Pictographic),
+    (0x1f93c, 0x1f945, Gcb::Pictographic),
+    (0x1f947, 0x1faff, Gcb::Pictographic),
+    (0x1fc00, 0x1fffd, Gcb::Pictographic),
+    (0xe0001, 0xe0001, Gcb::Control),
+    (0xe0020, 0xe007f, Gcb::Extend),
+    (0xe0100, 0xe01ef, Gcb::Extend),
+];
+
+/// Display width of every code point that isn't 1 cell wide, by range.
+pub const WIDTH: &[(u32, u32, u8)] = &[
+    (0x300, 0x36f, 0),
+    (0x378, 0x379, 2),
+    (0x380, 0x383, 2),
+    (0x38b, 0x38b, 2),
+    (0x38d, 0x38d, 2),
+    (0x3a2, 0x3a2, 2),
+    (0x483, 0x489, 0),
+    (0x530, 0x530, 2),
+    (0x557, 0x558, 2),
+    (0x58b, 0x58c, 2),
+    (0x590, 0x590, 2),
+    (0x591, 0x5bd, 0),
+    (0x5bf, 0x5bf, 0),
+    (0x5c1, 0x5c2, 0),
+    (0x5c4, 0x5c5, 0),
+    (0x5c7, 0x5c7, 0),
+    (0x5c8, 0x5cf, 2),
+    (0x5eb, 0x5ee, 2),
+    (0x5f5, 0x5ff, 2),
+    (0x600, 0x605, 0),
+    (0x610, 0x61a, 0),
+    (0x61c, 0x61c, 0),
+    (0x64b, 0x65f, 0),
+    (0x670, 0x670, 0),
+    (0x6d6, 0x6dd, 0),
+    (0x6df, 0x6e4, 0),
+    (0x6e7, 0x6e8, 0),
+    (0x6ea, 0x6ed, 0),
+    (0x70e, 0x70e, 2),
+    (0x70f, 0x70f, 0),
+    (0x711, 0x711, 0),
+    (0x730, 0x74a, 0),
+    (0x74b, 0x74c, 2),
+    (0x7a6, 0x7b0, 0),
+    (0x7b2, 0x7bf, 2),
+    (0x7eb, 0x7f3, 0),
+    (0x7fb, 0x7fc, 2),
+    (0x7fd, 0x7fd, 0),
+    (0x816, 0x819, 0),
+    (0x81b, 0x823, 0),
+    (0x825, 0x827, 0),
+    (0x829, 0x82d, 0),
+    (0x82e, 0x82f, 2),
+    (0x83f, 0x83f, 2),
+    (0x859, 0x85b, 0),
+    (0x85c, 0x85d, 2),
+    (0x85f, 0x85f, 2),
+    (0x86b, 0x86f, 2),
+    (0x88f, 0x88f, 2),
+    (0x890, 0x891, 0),
+    (0x892, 0x897, 2),
+    (0x898, 0x89f, 0),
+    (0x8ca, 0x902, 0),
+    (0x93a, 0x93a, 0),
+    (0x93c, 0x93c, 0),
+    (0x941, 0x948, 0),
+    (0x94d, 0x94d, 0),
+    (0x951, 0x957, 0),
+    (0x962, 0x963, 0),
+    (0x981, 0x981, 0),
+    (0x984, 0x984, 2),
+    (0x98d, 0x98e, 2),
+    (0x991, 0x992, 2),
+    (0x9a9, 0x9a9, 2),
+    (0x9b1, 0x9b1, 2),
+    (0x9b3, 0x9b5, 2),
+    (0x9ba, 0x9bb, 2),
+    (0x9bc, 0x9bc, 0),
+    (0x9c1, 0x9c4, 0),
+    (0x9c5, 0x9c6, 2),
+    (0x9c9, 0x9ca, 2),
+    (0x9cd, 0x9cd, 0),
+    (0x9cf, 0x9d6, 2),
+    (0x9d8, 0x9db, 2),
+    (0x9de, 0x9de, 2),
+    (0x9e2, 0x9e3, 0),
+    (0x9e4, 0x9e5, 2),
+    (0x9fe, 0x9fe, 0),
+    (0x9ff, 0xa00, 2),
+    (0xa01, 0xa02, 0),
+    (0xa04, 0xa04, 2),
+    (0xa0b, 0xa0e, 2),
+    (0xa11, 0xa12, 2),
+    (0xa29, 0xa29, 2),
+    (0xa31, 0xa31, 2),
+    (0xa34, 0xa34, 2),
+    (0xa37, 0xa37, 2),
+    (0xa3a, 0xa3b, 2),
+    (0xa3c, 0xa3c, 0),
+    (0xa3d, 0xa3d, 2),
+    (0xa41, 0xa42, 0),
+    (0xa43, 0xa46, 2),
+    (0xa47, 0xa48, 0),
+    (0xa49, 0xa4a, 2),
+    (0xa4b, 0xa4d, 0),
+    (0xa4e, 0xa50, 2),
+    (0xa51, 0xa51, 0),
+    (0xa52, 0xa58, 2),
+    (0xa5d, 0xa5d, 2),
+    (0xa5f, 0xa65, 2),
+    (0xa70, 0xa71, 0),
+    (0xa75, 0xa75, 0),
+    (0xa77, 0xa80, 2),
+    (0xa81, 0xa82, 0),
+    (0xa84, 0xa84, 2),
+    (0xa8e, 0xa8e, 2),
+    (0xa92, 0xa92, 2),
+    (0xaa9, 0xaa9, 2),
+    (0xab1, 0xab1, 2),
+    (0xab4, 0xab4, 2),
+    (0xaba, 0xabb, 2),
+    (0xabc, 0xabc, 0),
+    (0xac1, 0xac5, 0),
+    (0xac6, 0xac6, 2),
+    (0xac7, 0xac8, 0),
+    (0xaca, 0xaca, 2),
+    (0xacd, 0xacd, 0),
+    (0xace, 0xacf, 2),
+    (0xad1, 0xadf, 2),
+    (0xae2, 0xae3, 0),
+    (0xae4, 0xae5, 2),
+    (0xaf2, 0xaf8, 2),
+    (0xafa, 0xaff, 0),
+    (0xb00, 0xb00, 2),
+    (0xb01, 0xb01, 0),
+    (0xb04, 0xb04, 2),
+    (0xb0d, 0xb0e, 2),
+    (0xb11, 0xb12, 2),
+    (0xb29, 0xb29, 2),
+    (0xb31, 0xb31, 2),
+    (0xb34, 0xb34, 2),
+    (0xb3a, 0xb3b, 2),
+    (0xb3c, 0xb3c, 0),
+    (0xb3f, 0xb3f, 0),
+    (0xb41, 0xb44, 0),
+    (0xb45, 0xb46, 2),
+    (0xb49, 0xb4a, 2),
+    (0xb4d, 0xb4d, 0),
+    (0xb4e, 0xb54, 2),
+    (0xb55, 0xb56, 0),
+    (0xb58, 0xb5b, 2),
+    (0xb5e, 0xb5e, 2),
+    (0xb62, 0xb63, 0),
+    (0xb64, 0xb65, 2),
+    (0xb78, 0xb81, 2),
+    (0xb82, 0xb82, 0),
+    (0xb84, 0xb84, 2),
+    (0xb8b, 0xb8d, 2),
+    (0xb91, 0xb91, 2),
+    (0xb96, 0xb98, 2),
+    (0xb9b, 0xb9b, 2),
+    (0xb9d, 0xb9d, 2),
+    (0xba0, 0xba2, 2),
+    (0xba5, 0xba7, 2),
+    (0xbab, 0xbad, 2),
+    (0xbba, 0xbbd, 2),
+    (0xbc0, 0xbc0, 0),
+    (0xbc3, 0xbc5, 2),
+    (0xbc9, 0xbc9, 2),
+    (0xbcd, 0xbcd, 0),
+    (0xbce, 0xbcf, 2),
+    (0xbd1, 0xbd6, 2),
+    (0xbd8, 0xbe5, 2),
+    (0xbfb, 0xbff, 2),
+    (0xc00, 0xc00, 0),
+    (0xc04, 0xc04, 0),
+    (0xc0d, 0xc0d, 2),
+    (0xc11, 0xc11, 2),
+    (0xc29, 0xc29, 2),
+    (0xc3a, 0xc3b, 2),
+    (0xc3c, 0xc3c, 0),
+    (0xc3e, 0xc40, 0),
+    (0xc45, 0xc45, 2),
+    (0xc46, 0xc48, 0),
+    (0xc49, 0xc49, 2),
+    (0xc4a, 0xc4d, 0),
+    (0xc4e, 0xc54, 2),
+    (0xc55, 0xc56, 0),
+    (0xc57, 0xc57, 2),
+    (0xc5b, 0xc5c, 2),
+    (0xc5e, 0xc5f, 2),
+    (0xc62, 0xc63, 0),
+    (0xc64, 0xc65, 2),
+    (0xc70, 0xc76, 2),
+    (0xc81, 0xc81, 0),
+    (0xc8d, 0xc8d, 2),
+    (0xc91, 0xc91, 2),
+    (0xca9, 0xca9, 2),
+    (0xcb4, 0xcb4, 2),
+    (0xcba, 0xcbb, 2),
+    (0xcbc, 0xcbc, 0),
+    (0xcbf, 0xcbf, 0),
+    (0xcc5, 0xcc5, 2),
+    (0xcc6, 0xcc6, 0),
+    (0xcc9, 0xcc9, 2),
+    (0xccc, 0xccd, 0),
+    (0xcce, 0xcd4, 2),
+    (0xcd7, 0xcdc, 2),
+    (0xcdf, 0xcdf, 2),
+    (0xce2, 0xce3, 0),
+    (0xce4, 0xce5, 2),
+    (0xcf0, 0xcf0, 2),
+    (0xcf3, 0xcff, 2),
+    (0xd00, 0xd01, 0),
+    (0xd0d, 0xd0d, 2),
+    (0xd11, 0xd11, 2),
+    (0xd3b, 0xd3c, 0),
+    (0xd41, 0xd44, 0),
+    (0xd45, 0xd45, 2),
+    (0xd49, 0xd49, 2),
+    (0xd4d, 0xd4d, 0),
+    (0xd50, 0xd53, 2),
+    (0xd62, 0xd63, 0),
+    (0xd64, 0xd65, 2),
+    (0xd80, 0xd80, 2),
+    (0xd81, 0xd81, 0),
+    (0xd84, 0xd84, 2),
+    (0xd97, 0xd99, 2),
+    (0xdb2, 0xdb2, 2),
+    (0xdbc, 0xdbc, 2),
+    (0xdbe, 0xdbf, 2),
+    (0xdc7, 0xdc9, 2),
+    (0xdca, 0xdca, 0),
+    (0xdcb, 0xdce, 2),
+    (0xdd2, 0xdd4, 0),
+    (0xdd5, 0xdd5, 2),
+    (0xdd6, 0xdd6, 0),
+    (0xdd7, 0xdd7, 2),
+    (0xde0, 0xde5, 2),
+    (0xdf0, 0xdf1, 2),
+    (0xdf5, 0xe00, 2),
+    (0xe31, 0xe31, 0),
+    (0xe34, 0xe3a, 0),
+    (0xe3b, 0xe3e, 2),
+    (0xe47, 0xe4e, 0),
+    (0xe5c, 0xe80, 2),
+    (0xe83, 0xe83, 2),
+    (0xe85, 0xe85, 2),
+    (0xe8b, 0xe8b, 2),
+    (0xea4, 0xea4, 2),
+    (0xea6, 0xea6, 2),
+    (0xeb1, 0xeb1, 0),
+    (0xeb4, 0xebc, 0),
+    (0xebe, 0xebf, 2),
+    (0xec5, 0xec5, 2),
+    (0xec7, 0xec7, 2),
+    (0xec8, 0xecd, 0),
+    (0xece, 0xecf, 2),
+    (0xeda, 0xedb, 2),
+    (0xee0, 0xeff, 2),
+    (0xf18, 0xf19, 0),
+    (0xf35, 0xf35, 0),
+    (0xf37, 0xf37, 0),
+    (0xf39, 0xf39, 0),
+    (0xf48, 0xf48, 2),
+    (0xf6d, 0xf70, 2),
+    (0xf71, 0xf7e, 0),
+    (0xf80, 0xf84, 0),
+    (0xf86, 0xf87, 0),
+    (0xf8d, 0xf97, 0),
+    (0xf98, 0xf98, 2),
+    (0xf99, 0xfbc, 0),
+    (0xfbd, 0xfbd, 2),
+    (0xfc6, 0xfc6, 0),
+    (0xfcd, 0xfcd, 2),
+    (0xfdb, 0xfff, 2),
+    (0x102d, 0x1030, 0),
+    (0x1032, 0x1037, 0),
+    (0x1039, 0x103a, 0),
+    (0x103d, 0x103e, 0),
+    (0x1058, 0x1059, 0),
+    (0x105e, 0x1060, 0),
+    (0x1071, 0x1074, 0),
+    (0x1082, 0x1082, 0),
+    (0x1085, 0x1086, 0),
+    (0x108d, 0x108d, 0),
+    (0x109d, 0x109d, 0),
+    (0x10c6, 0x10c6, 2),
+    (0x10c8, 0x10cc, 2),
+    (0x10ce, 0x10cf, 2),
+    (0x1100, 0x115f, 2),
+    (0x1160, 0x11ff, 0),
+    (0x1249, 0x1249, 2),
+    (0x124e, 0x124f, 2),
+    (0x1257, 0x1257, 2),
+    (0x1259, 0x1259, 2),
+    (0x125e, 0x125f, 2),
+    (0x1289, 0x1289, 2),
+    (0x128e, 0x128f, 2),
+    (0x12b1, 0x12b1, 2),
+    (0x12b6, 0x12b7, 2),
+    (0x12bf, 0x12bf, 2),
+    (0x12c1, 0x12c1, 2),
+    (0x12c6, 0x12c7, 2),
+    (0x12d7, 0x12d7, 2),
+    (0x1311, 0x1311, 2),
+    (0x1316, 0x1317, 2),
+    (0x135b, 0x135c, 2),
+    (0x135d, 0x135f, 0),
+    (0x137d, 0x137f, 2),
+    (0x139a, 0x139f, 2),
+    (0x13f6, 0x13f7, 2),
+    (0x13fe, 0x13ff, 2),
+    (0x169d, 0x169f, 2),
+    (0x16f9, 0x16ff, 2),
+    (0x1712, 0x1714, 0),
+    (0x1716, 0x171e, 2),
+    (0x1732, 0x1733, 0),
+    (0x1737, 0x173f, 2),
+    (0x1752, 0x1753, 0),
+    (0x1754, 0x175f, 2),
+    (0x176d, 0x176d, 2),
+    (0x1771, 0x1771, 2),
+    (0x1772, 0x1773, 0),
+    (0x1774, 0x177f, 2),
+    (0x17b4, 0x17b5, 0),
+    (0x17b7, 0x17bd, 0),
+    (0x17c6, 0x17c6, 0),
+    (0x17c9, 0x17d3, 0),
+    (0x17dd, 0x17dd, 0),
+    (0x17de, 0x17df, 2),
+    (0x17ea, 0x17ef, 2),
+    (0x17fa, 0x17ff, 2),
+    (0x180b, 0x180f, 0),
+    (0x181a, 0x181f, 2),
+    (0x1879, 0x187f, 2),
+    (0x1885, 0x1886, 0),
+    (0x18a9, 0x18a9, 0),
+    (0x18ab, 0x18af, 2),
+    (0x18f6, 0x18ff, 2),
+    (0x191f, 0x191f, 2),
+    (0x1920, 0x1922, 0),
+    (0x1927, 0x1928, 0),
+    (0x192c, 0x192f, 2),
+    (0x1932, 0x1932, 0),
+    (0x1939, 0x193b, 0),
+    (0x193c, 0x193f, 2),
+    (0x1941, 0x1943, 2),
+    (0x196e, 0x196f, 2),
+    (0x1975, 0x197f, 2),
+    (0x19ac, 0x19af, 2),
+    (0x19ca, 0x19cf, 2),
+    (0x19db, 0x19dd, 2),
+    (0x1a17, 0x1a18, 0),
+    (0x1a1b, 0x1a1b, 0),
+    (0x1a1c, 0x1a1d, 2),
+    (0x1a56, 0x1a56, 0),
+    (0x1a58, 0x1a5e, 0),
+    (0x1a5f, 0x1a5f, 2),
+    (0x1a60, 0x1a60, 0),
+    (0x1a62, 0x1a62, 0),
+    (0x1a65, 0x1a6c, 0),
+    (0x1a73, 0x1a7c, 0),
+    (0x1a7d, 0x1a7e, 2),
+    (0x1a7f, 0x1a7f, 0),
+    (0x1a8a, 0x1a8f, 2),
+    (0x1a9a, 0x1a9f, 2),
+    (0x1aae, 0x1aaf, 2),
+    (0x1ab0, 0x1ace, 0),
+    (0x1acf, 0x1aff, 2),
+    (0x1b00, 0x1b03, 0),
+    (0x1b34, 0x1b34, 0),
+    (0x1b36, 0x1b3a, 0),
+    (0x1b3c, 0x1b3c, 0),
+    (0x1b42, 0x1b42, 0),
+    (0x1b4d, 0x1b4f, 2),
+    (0x1b6b, 0x1b73, 0),
+    (0x1b7f, 0x1b7f, 2),
+    (0x1b80, 0x1b81, 0),
+    (0x1ba2, 0x1ba5, 0),
+    (0x1ba8, 0x1ba9, 0),
+    (0x1bab, 0x1bad, 0),
+    (0x1be6, 0x1be6, 0),
+    (0x1be8, 0x1be9, 0),
+    (0x1bed, 0x1bed, 0),
+    (0x1bef, 0x1bf1, 0),
+    (0x1bf4, 0x1bfb, 2),
+    (0x1c2c, 0x1c33, 0),
+    (0x1c36, 0x1c37, 0),
+    (0x1c38, 0x1c3a, 2),
+    (0x1c4a, 0x1c4c, 2),
+    (0x1c89, 0x1c8f, 2),
+    (0x1cbb, 0x1cbc, 2),
+    (0x1cc8, 0x1ccf, 2),
+    (0x1cd0, 0x1cd2, 0),
+    (0x1cd4, 0x1ce0, 0),
+    (0x1ce2, 0x1ce8, 0),
+    (0x1ced, 0x1ced, 0),
+    (0x1cf4, 0x1cf4, 0),
+    (0x1cf8, 0x1cf9, 0),
+    (0x1cfb, 0x1cff, 2),
+    (0x1dc0, 0x1dff, 0),
+    (0x1f16, 0x1f17, 2),
+    (0x1f1e, 0x1f1f, 2),
+    (0x1f46, 0x1f47, 2),
+    (0x1f4e, 0x1f4f, 2),
+    (0x1f58, 0x1f58, 2),
+    (0x1f5a, 0x1f5a, 2),
+    (0x1f5c, 0x1f5c, 2),
+    (0x1f5e, 0x1f5e, 2),
+    (0x1f7e, 0x1f7f, 2),
+    (0x1fb5, 0x1fb5, 2),
+    (0x1fc5, 0x1fc5, 2),
+    (0x1fd4, 0x1fd5, 2),
+    (0x1fdc, 0x1fdc, 2),
+    (0x1ff0, 0x1ff1, 2),
+    (0x1ff5, 0x1ff5, 2),
+    (0x1fff, 0x1fff, 2),
+    (0x200b, 0x200f, 0),
+    (0x202a, 0x202e, 0),
+    (0x2060, 0x2064, 0),
+    (0x2065, 0x2065, 2),
+    (0x2066, 0x206f, 0),
+    (0x2072, 0x2073, 2),
+    (0x208f, 0x208f, 2),
+    (0x209d, 0x209f, 2),
+    (0x20c1, 0x20cf, 2),
+    (0x20d0, 0x20f0, 0),
+    (0x20f1, 0x20ff, 2),
+    (0x218c, 0x218f, 2),
+    (0x231a, 0x231b, 2),
+    (0x2329, 0x232a, 2),
+    (0x23e9, 0x23ec, 2),
+    (0x23f0, 0x23f0, 2),
+    (0x23f3, 0x23f3, 2),
+    (0x2427, 0x243f, 2),
+    (0x244b, 0x245f, 2),
+    (0x25fd, 0x25fe, 2),
+    (0x2614, 0x2615, 2),
+    (0x2648, 0x2653, 2),
+    (0x267f, 0x267f, 2),
+    (0x2693, 0x2693, 2),
+    (0x26a1, 0x26a1, 2),
+    (0x26aa, 0x26ab, 2),
+    (0x26bd, 0x26be, 2),
+    (0x26c4, 0x26c5, 2),
+    (0x26ce, 0x26ce, 2),
+    (0x26d4, 0x26d4, 2),
+    (0x26ea, 0x26ea, 2),
+    (0x26f2, 0x26f3, 2),
+    (0x26f5, 0x26f5, 2),
+    (0x26fa, 0x26fa, 2),
+    (0x26fd, 0x26fd, 2),
+    (0x2705, 0x2705, 2),
+    (0x270a, 0x270b, 2),
+    (0x2728, 0x2728, 2),
+    (0x274c, 0x274c, 2),
+    (0x274e, 0x274e, 2),
+    (0x2753, 0x2755, 2),
+    (0x2757, 0x2757, 2),
+    (0x2795, 0x2797, 2),
+    (0x27b0, 0x27b0, 2),
+    (0x27bf, 0x27bf, 2),
+    (0x2b1b, 0x2b1c, 2),
+    (0x2b50, 0x2b50, 2),
+    (0x2b55, 0x2b55, 2),
+    (0x2b74, 0x2b75, 2),
+    (0x2b96, 0x2b96, 2),
+    (0x2cef, 0x2cf1, 0),
+    (0x2cf4, 0x2cf8, 2),
+    (0x2d26, 0x2d26, 2),
+    (0x2d28, 0x2d2c, 2),
+    (0x2d2e, 0x2d2f, 2),
+    (0x2d68, 0x2d6e, 2),
+    (0x2d71, 0x2d7e, 2),
+    (0x2d7f, 0x2d7f, 0),
+    (0x2d97, 0x2d9f, 2),
+    (0x2da7, 0x2da7, 2),
+    (0x2daf, 0x2daf, 2),
+    (0x2db7, 0x2db7, 2),
+    (0x2dbf, 0x2dbf, 2),
+    (0x2dc7, 0x2dc7, 2),
+    (0x2dcf, 0x2dcf, 2),
+    (0x2dd7, 0x2dd7, 2),
+    (0x2ddf, 0x2ddf, 2),
+    (0x2de0, 0x2dff, 0),
+    (0x2e5e, 0x3029, 2),
+    (0x302a, 0x302d, 0),
+    (0x302e, 0x303e, 2),
+    (0x3040, 0x3098, 2),
+    (0x3099, 0x309a, 0),
+    (0x309b, 0x3247, 2),
+    (0x3250, 0x4dbf, 2),
+    (0x4e00, 0xa4cf, 2),
+    (0xa62c, 0xa63f, 2),
+    (0xa66f, 0xa672, 0),
+    (0xa674, 0xa67d, 0),
+    (0xa69e, 0xa69f, 0),
+    (0xa6f0, 0xa6f1, 0),
+    (0xa6f8, 0xa6ff, 2),
+    (0xa7cb, 0xa7cf, 2),
+    (0xa7d2, 0xa7d2, 2),
+    (0xa7d4, 0xa7d4, 2),
+    (0xa7da, 0xa7f1, 2),
+    (0xa802, 0xa802, 0),
+    (0xa806, 0xa806, 0),
+    (0xa80b, 0xa80b, 0),
+    (0xa825, 0xa826, 0),
+    (0xa82c, 0xa82c, 0),
+    (0xa82d, 0xa82f, 2),
+    (0xa83a, 0xa83f, 2),
+    (0xa878, 0xa87f, 2),
+    (0xa8c4, 0xa8c5, 0),
+    (0xa8c6, 0xa8cd, 2),
+    (0xa8da, 0xa8df, 2),
+    (0xa8e0, 0xa8f1, 0),
+    (0xa8ff, 0xa8ff, 0),
+    (0xa926, 0xa92d, 0),
+    (0xa947, 0xa951, 0),
+    (0xa954, 0xa95e, 2),
+    (0xa960, 0xa97f, 2),
+    (0xa980, 0xa982, 0),
+    (0xa9b3, 0xa9b3, 0),
+    (0xa9b6, 0xa9b9, 0),
+    (0xa9bc, 0xa9bd, 0),
+    (0xa9ce, 0xa9ce, 2),
+    (0xa9da, 0xa9dd, 2),
+    (0xa9e5, 0xa9e5, 0),
+    (0xa9ff, 0xa9ff, 2),
+    (0xaa29, 0xaa2e, 0),
+    (0xaa31, 0xaa32, 0),
+    (0xaa35, 0xaa36, 0),
+    (0xaa37, 0xaa3f, 2),
+    (0xaa43, 0xaa43, 0),
+    (0xaa4c, 0xaa4c, 0),
+    (0xaa4e, 0xaa4f, 2),
+    (0xaa5a, 0xaa5b, 2),
+    (0xaa7c, 0xaa7c, 0),
+    (0xaab0, 0xaab0, 0),
+    (0xaab2, 0xaab4, 0),
+    (0xaab7, 0xaab8, 0),
+    (0xaabe, 0xaabf, 0),
+    (0xaac1, 0xaac1, 0),
+    (0xaac3, 0xaada, 2),
+    (0xaaec, 0xaaed, 0),
+    (0xaaf6, 0xaaf6, 0),
+    (0xaaf7, 0xab00, 2),
+    (0xab07, 0xab08, 2),
+    (0xab0f, 0xab10, 2),
+    (0xab17, 0xab1f, 2),
+    (0xab27, 0xab27, 2),
+    (0xab2f, 0xab2f, 2),
+    (0xab6c, 0xab6f, 2),
+    (0xabe5, 0xabe5, 0),
+    (0xabe8, 0xabe8, 0),
+    (0xabed, 0xabed, 0),
+    (0xabee, 0xabef, 2),
+    (0xabfa, 0xd7af, 2),
+    (0xd7b0, 0xd7ff, 0),
+    (0xf900, 0xfaff, 2),
+    (0xfb07, 0xfb12, 2),
+    (0xfb18, 0xfb1c, 2),
+    (0xfb1e, 0xfb1e, 0),
+    (0xfb37, 0xfb37, 2),
+    (0xfb3d, 0xfb3d, 2),
+    (0xfb3f, 0xfb3f, 2),
+    (0xfb42, 0xfb42, 2),
+    (0xfb45, 0xfb45, 2),
+    (0xfbc3, 0xfbd2, 2),
+    (0xfd90, 0xfd91, 2),
+    (0xfdc8, 0xfdce, 2),
+    (0xfdd0, 0xfdef, 2),
+    (0xfe00, 0xfe0f, 0),
+    (0xfe10, 0xfe1f, 2),
+    (0xfe20, 0xfe2f, 0),
+    (0xfe30, 0xfe6f, 2),
+    (0xfe75, 0xfe75, 2),
+    (0xfefd, 0xfefe, 2),
+    (0xfeff, 0xfeff, 0),
+    (0xff00, 0xff60, 2),
+    (0xffbf, 0xffc1, 2),
+    (0xffc8, 0xffc9, 2),
+    (0xffd0, 0xffd1, 2),
+    (0xffd8, 0xffd9, 2),
+    (0xffdd, 0xffe7, 2),
+    (0xffef, 0xfff8, 2),
+    (0xfff9, 0xfffb, 0),
+    (0xfffe, 0xffff, 2),
+    (0x1000c, 0x1000c, 2),
+    (0x10027, 0x10027, 2),
+    (0x1003b, 0x1003b, 2),
+    (0x1003e, 0x1003e, 2),
+    (0x1004e, 0x1004f, 2),
+    (0x1005e, 0x1007f, 2),
+    (0x100fb, 0x100ff, 2),
+    (0x10103, 0x10106, 2),
+    (0x10134, 0x10136, 2),
+    (0x1018f, 0x1018f, 2),
+    (0x1019d, 0x1019f, 2),
+    (0x101a1, 0x101cf, 2),
+    (0x101fd, 0x101fd, 0),
+    (0x101fe, 0x1027f, 2),
+    (0x1029d, 0x1029f, 2),
+    (0x102d1, 0x102df, 2),
+    (0x102e0, 0x102e0, 0),
+    (0x102fc, 0x102ff, 2),
+    (0x10324, 0x1032c, 2),
+    (0x1034b, 0x1034f, 2),
+    (0x10376, 0x1037a, 0),
+    (0x1037b, 0x1037f, 2),
+    (0x1039e, 0x1039e, 2),
+    (0x103c4, 0x103c7, 2),
+    (0x103d6, 0x103ff, 2),
+    (0x1049e, 0x1049f, 2),
+    (0x104aa, 0x104af, 2),
+    (0x104d4, 0x104d7, 2),
+    (0x104fc, 0x104ff, 2),
+    (0x10528, 0x1052f, 2),
+    (0x10564, 0x1056e, 2),
+    (0x1057b, 0x1057b, 2),
+    (0x1058b, 0x1058b, 2),
+    (0x10593, 0x10593, 2),
+    (0x10596, 0x10596, 2),
+    (0x105a2, 0x105a2, 2),
+    (0x105b2, 0x105b2, 2),
+    (0x105ba, 0x105ba, 2),
+    (0x105bd, 0x105ff, 2),
+    (0x10737, 0x1073f, 2),
+    (0x10756, 0x1075f, 2),
+    (0x10768, 0x1077f, 2),
+    (0x10786, 0x10786, 2),
+    (0x107b1, 0x107b1, 2),
+    (0x107bb, 0x107ff, 2),
+    (0x10806, 0x10807, 2),
+    (0x10809, 0x10809, 2),
+    (0x10836, 0x10836, 2),
+    (0x10839, 0x1083b, 2),
+    (0x1083d, 0x1083e, 2),
+    (0x10856, 0x10856, 2),
+    (0x1089f, 0x108a6, 2),
+    (0x108b0, 0x108df, 2),
+    (0x108f3, 0x108f3, 2),
+    (0x108f6, 0x108fa, 2),
+    (0x1091c, 0x1091e, 2),
+    (0x1093a, 0x1093e, 2),
+    (0x10940, 0x1097f, 2),
+    (0x109b8, 0x109bb, 2),
+    (0x109d0, 0x109d1, 2),
+    (0x10a01, 0x10a03, 0),
+    (0x10a04, 0x10a04, 2),
+    (0x10a05, 0x10a06, 0),
+    (0x10a07, 0x10a0b, 2),
+    (0x10a0c, 0x10a0f, 0),
+    (0x10a14, 0x10a14, 2),
+    (0x10a18, 0x10a18, 2),
+    (0x10a36, 0x10a37, 2),
+    (0x10a38, 0x10a3a, 0),
+    (0x10a3b, 0x10a3e, 2),
+    (0x10a3f, 0x10a3f, 0),
+    (0x10a49, 0x10a4f, 2),
+    (0x10a59, 0x10a5f, 2),
+    (0x10aa0, 0x10abf, 2),
+    (0x10ae5, 0x10ae6, 0),
+    (0x10ae7, 0x10aea, 2),
+    (0x10af7, 0x10aff, 2),
+    (0x10b36, 0x10b38, 2),
+    (0x10b56, 0x10b57, 2),
+    (0x10b73, 0x10b77, 2),
+    (0x10b92, 0x10b98, 2),
+    (0x10b9d, 0x10ba8, 2),
+    (0x10bb0, 0x10bff, 2),
+    (0x10c49, 0x10c7f, 2),
+    (0x10cb3, 0x10cbf, 2),
+    (0x10cf3, 0x10cf9, 2),
+    (0x10d24, 0x10d27, 0),
+    (0x10d28, 0x10d2f, 2),
+    (0x10d3a, 0x10e5f, 2),
+    (0x10e7f, 0x10e7f, 2),
+    (0x10eaa, 0x10eaa, 2),
+    (0x10eab, 0x10eac, 0),
+    (0x10eae, 0x10eaf, 2),
+    (0x10eb2, 0x10eff, 2),
+    (0x10f28, 0x10f2f, 2),
+    (0x10f46, 0x10f50, 0),
+    (0x10f5a, 0x10f6f, 2),
+    (0x10f82, 0x10f85, 0),
+    (0x10f8a, 0x10faf, 2),
+    (0x10fcc, 0x10fdf, 2),
+    (0x10ff7, 0x10fff, 2),
+    (0x11001, 0x11001, 0),
+    (0x11038, 0x11046, 0),
+    (0x1104e, 0x11051, 2),
+    (0x11070, 0x11070, 0),
+    (0x11073, 0x11074, 0),
+    (0x11076, 0x1107e, 2),
+    (0x1107f, 0x11081, 0),
+    (0x110b3, 0x110b6, 0),
+    (0x110b9, 0x110ba, 0),
+    (0x110bd, 0x110bd, 0),
+    (0x110c2, 0x110c2, 0),
+    (0x110c3, 0x110cc, 2),
+    (0x110cd, 0x110cd, 0),
+    (0x110ce, 0x110cf, 2),
+    (0x110e9, 0x110ef, 2),
+    (0x110fa, 0x110ff, 2),
+    (0x11100, 0x11102, 0),
+    (0x11127, 0x1112b, 0),
+    (0x1112d, 0x11134, 0),
+    (0x11135, 0x11135, 2),
+    (0x11148, 0x1114f, 2),
+    (0x11173, 0x11173, 0),
+    (0x11177, 0x1117f, 2),
+    (0x11180, 0x11181, 0),
+    (0x111b6, 0x111be, 0),
+    (0x111c9, 0x111cc, 0),
+    (0x111cf, 0x111cf, 0),
+    (0x111e0, 0x111e0, 2),
+    (0x111f5, 0x111ff, 2),
+    (0x11212, 0x11212, 2),
+    (0x1122f, 0x11231, 0),
+    (0x11234, 0x11234, 0),
+    (0x11236, 0x11237, 0),
+    (0x1123e, 0x1123e, 0),
+    (0x1123f, 0x1127f, 2),
+    (0x11287, 0x11287, 2),
+    (0x11289, 0x11289, 2),
+    (0x1128e, 0x1128e, 2),
+    (0x1129e, 0x1129e, 2),
+    (0x112aa, 0x112af, 2),
+    (0x112df, 0x112df, 0),
+    (0x112e3, 0x112ea, 0),
+    (0x112eb, 0x112ef, 2),
+    (0x112fa, 0x112ff, 2),
+    (0x11300, 0x11301, 0),
+    (0x11304, 0x11304, 2),
+    (0x1130d, 0x1130e, 2),
+    (0x11311, 0x11312, 2),
+    (0x11329, 0x11329, 2),
+    (0x11331, 0x11331, 2),
+    (0x11334, 0x11334, 2),
+    (0x1133a, 0x1133a, 2),
+    (0x1133b, 0x1133c, 0),
+    (0x11340, 0x11340, 0),
+    (0x11345, 0x11346, 2),
+    (0x11349, 0x1134a, 2),
+    (0x1134e, 0x1134f, 2),
+    (0x11351, 0x11356, 2),
+    (0x11358, 0x1135c, 2),
+    (0x11364, 0x11365, 2),
+    (0x11366, 0x1136c, 0),
+    (0x1136d, 0x1136f, 2),
+    (0x11370, 0x11374, 0),
+    (0x11375, 0x113ff, 2),
+    (0x11438, 0x1143f, 0),
+    (0x11442, 0x11444, 0),
+    (0x11446, 0x11446, 0),
+    (0x1145c, 0x1145c, 2),
+    (0x1145e, 0x1145e, 0),
+    (0x11462, 0x1147f, 2),
+    (0x114b3, 0x114b8, 0),
+    (0x114ba, 0x114ba, 0),
+    (0x114bf, 0x114c0, 0),
+    (0x114c2, 0x114c3, 0),
+    (0x114c8, 0x114cf, 2),
+    (0x114da, 0x1157f, 2),
+    (0x115b2, 0x115b5, 0),
+    (0x115b6, 0x115b7, 2),
+    (0x115bc, 0x115bd, 0),
+    (0x115bf, 0x115c0, 0),
+    (0x115dc, 0x115dd, 0),
+    (0x115de, 0x115ff, 2),
+    (0x11633, 0x1163a, 0),
+    (0x1163d, 0x1163d, 0),
+    (0x1163f, 0x11640, 0),
+    (0x11645, 0x1164f, 2),
+    (0x1165a, 0x1165f, 2),
+    (0x1166d, 0x1167f, 2),
+    (0x116ab, 0x116ab, 0),
+    (0x116ad, 0x116ad, 0),
+    (0x116b0, 0x116b5, 0),
+    (0x116b7, 0x116b7, 0),
+    (0x116ba, 0x116bf, 2),
+    (0x116ca, 0x116ff, 2),
+    (0x1171b, 0x1171c, 2),
+    (0x1171d, 0x1171f, 0),
+    (0x11722, 0x11725, 0),
+    (0x11727, 0x1172b, 0),
+    (0x1172c, 0x1172f, 2),
+    (0x11747, 0x117ff, 2),
+    (0x1182f, 0x11837, 0),
+    (0x11839, 0x1183a, 0),
+    (0x1183c, 0x1189f, 2),
+    (0x118f3, 0x118fe, 2),
+    (0x11907, 0x11908, 2),
+    (0x1190a, 0x1190b, 2),
+    (0x11914, 0x11914, 2),
+    (0x11917, 0x11917, 2),
+    (0x11936, 0x11936, 2),
+    (0x11939, 0x1193a, 2),
+    (0x1193b, 0x1193c, 0),
+    (0x1193e, 0x1193e, 0),
+    (0x11943, 0x11943, 0),
+    (0x11947, 0x1194f, 2),
+    (0x1195a, 0x1199f, 2),
+    (0x119a8, 0x119a9, 2),
+    (0x119d4, 0x119d7, 0),
+    (0x119d8, 0x119d9, 2),
+    (0x119da, 0x119db, 0),
+    (0x119e0, 0x119e0, 0),
+    (0x119e5, 0x119ff, 2),
+    (0x11a01, 0x11a0a, 0),
+    (0x11a33, 0x11a38, 0),
+    (0x11a3b, 0x11a3e, 0),
+    (0x11a47, 0x11a47, 0),
+    (0x11a48, 0x11a4f, 2),
+    (0x11a51, 0x11a56, 0),
+    (0x11a59, 0x11a5b, 0),
+    (0x11a8a, 0x11a96, 0),
+    (0x11a98, 0x11a99, 0),
+    (0x11aa3, 0x11aaf, 2),
+    (0x11af9, 0x11bff, 2),
+    (0x11c09, 0x11c09, 2),
+    (0x11c30, 0x11c36, 0),
+    (0x11c37, 0x11c37, 2),
+    (0x11c38, 0x11c3d, 0),
+    (0x11c3f, 0x11c3f, 0),
+    (0x11c46, 0x11c4f, 2),
+    (0x11c6d, 0x11c6f, 2),
+    (0x11c90, 0x11c91, 2),
+    (0x11c92, 0x11ca7, 0),
+    (0x11ca8, 0x11ca8, 2),
+    (0x11caa, 0x11cb0, 0),
+    (0x11cb2, 0x11cb3, 0),
+    (0x11cb5, 0x11cb6, 0),
+    (0x11cb7, 0x11cff, 2),
+    (0x11d07, 0x11d07, 2),
+    (0x11d0a, 0x11d0a, 2),
+    (0x11d31, 0x11d36, 0),
+    (0x11d37, 0x11d39, 2),
+    (0x11d3a, 0x11d3a, 0),
+    (0x11d3b, 0x11d3b, 2),
+    (0x11d3c, 0x11d3d, 0),
+    (0x11d3e, 0x11d3e, 2),
+    (0x11d3f, 0x11d45, 0),
+    (0x11d47, 0x11d47, 0),
+    (0x11d48, 0x11d4f, 2),
+    (0x11d5a, 0x11d5f, 2),
+    (0x11d66, 0x11d66, 2),
+    (0x11d69, 0x11d69, 2),
+    (0x11d8f, 0x11d8f, 2),
+    (0x11d90, 0x11d91, 0),
+    (0x11d92, 0x11d92, 2),
+    (0x11d95, 0x11d95, 0),
+    (0x11d97, 0x11d97, 0),
+    (0x11d99, 0x11d9f, 2),
+    (0x11daa, 0x11edf, 2),
+    (0x11ef3, 0x11ef4, 0),
+    (0x11ef9, 0x11faf, 2),
+    (0x11fb1, 0x11fbf, 2),
+    (0x11ff2, 0x11ffe, 2),
+    (0x1239a, 0x123ff, 2),
+    (0x1246f, 0x1246f, 2),
+    (0x12475, 0x1247f, 2),
+    (0x12544, 0x12f8f, 2),
+    (0x12ff3, 0x12fff, 2),
+    (0x1342f, 0x1342f, 2),
+    (0x13430, 0x13438, 0),
+    (0x13439, 0x143ff, 2),
+    (0x14647, 0x167ff, 2),
+    (0x16a39, 0x16a3f, 2),
+    (0x16a5f, 0x16a5f, 2),
+    (0x16a6a, 0x16a6d, 2),
+    (0x16abf, 0x16abf, 2),
+    (0x16aca, 0x16acf, 2),
+    (0x16aee, 0x16aef, 2),
+    (0x16af0, 0x16af4, 0),
+    (0x16af6, 0x16aff, 2),
+    (0x16b30, 0x16b36, 0),
+    (0x16b46, 0x16b4f, 2),
+    (0x16b5a, 0x16b5a, 2),
+    (0x16b62, 0x16b62, 2),
+    (0x16b78, 0x16b7c, 2),
+    (0x16b90, 0x16e3f, 2),
+    (0x16e9b, 0x16eff, 2),
+    (0x16f4b, 0x16f4e, 2),
+    (0x16f4f, 0x16f4f, 0),
+    (0x16f88, 0x16f8e, 2),
+    (0x16f8f, 0x16f92, 0),
+    (0x16fa0, 0x16fe3, 2),
+    (0x16fe4, 0x16fe4, 0),
+    (0x16fe5, 0x1bbff, 2),
+    (0x1bc6b, 0x1bc6f, 2),
+    (0x1bc7d, 0x1bc7f, 2),
+    (0x1bc89, 0x1bc8f, 2),
+    (0x1bc9a, 0x1bc9b, 2),
+    (0x1bc9d, 0x1bc9e, 0),
+    (0x1bca0, 0x1bca3, 0),
+    (0x1bca4, 0x1ceff, 2),
+    (0x1cf00, 0x1cf2d, 0),
+    (0x1cf2e, 0x1cf2f, 2),
+    (0x1cf30, 0x1cf46, 0),
+    (0x1cf47, 0x1cf4f, 2),
+    (0x1cfc4, 0x1cfff, 2),
+    (0x1d0f6, 0x1d0ff, 2),
+    (0x1d127, 0x1d128, 2),
+    (0x1d167, 0x1d169, 0),
+    (0x1d173, 0x1d182, 0),
+    (0x1d185, 0x1d18b, 0),
+    (0x1d1aa, 0x1d1ad, 0),
+    (0x1d1eb, 0x1d1ff, 2),
+    (0x1d242, 0x1d244, 0),
+    (0x1d246, 0x1d2df, 2),
+    (0x1d2f4, 0x1d2ff, 2),
+    (0x1d357, 0x1d35f, 2),
+    (0x1d379, 0x1d3ff, 2),
+    (0x1d455, 0x1d455, 2),
+    (0x1d49d, 0x1d49d, 2),
+    (0x1d4a0, 0x1d4a1, 2),
+    (0x1d4a3, 0x1d4a4, 2),
+    (0x1d4a7, 0x1d4a8, 2),
+    (0x1d4ad, 0x1d4ad, 2),
+    (0x1d4ba, 0x1d4ba, 2),
+    (0x1d4bc, 0x1d4bc, 2),
+    (0x1d4c4, 0x1d4c4, 2),
+    (0x1d506, 0x1d506, 2),
+    (0x1d50b, 0x1d50c, 2),
+    (0x1d515, 0x1d515, 2),
+    (0x1d51d, 0x1d51d, 2),
+    (0x1d53a, 0x1d53a, 2),
+    (0x1d53f, 0x1d53f, 2),
+    (0x1d545, 0x1d545, 2),
+    (0x1d547, 0x1d549, 2),
+    (0x1d551, 0x1d551, 2),
+    (0x1d6a6, 0x1d6a7, 2),
+    (0x1d7cc, 0x1d7cd, 2),
+    (0x1da00, 0x1da36, 0),
+    (0x1da3b, 0x1da6c, 0),
+    (0x1da75, 0x1da75, 0),
+    (0x1da84, 0x1da84, 0),
+    (0x1da8c, 0x1da9a, 2),
+    (0x1da9b, 0x1da9f, 0),
+    (0x1daa0, 0x1daa0, 2),
+    (0x1daa1, 0x1daaf, 0),
+    (0x1dab0, 0x1deff, 2),
+    (0x1df1f, 0x1dfff, 2),
+    (0x1e000, 0x1e006, 0),
+    (0x1e007, 0x1e007, 2),
+    (0x1e008, 0x1e018, 0),
+    (0x1e019, 0x1e01a, 2),
+    (0x1e01b, 0x1e021, 0),
+    (0x1e022, 0x1e022, 2),
+    (0x1e023, 0x1e024, 0),
+    (0x1e025, 0x1e025, 2),
+    (0x1e026, 0x1e02a, 0),
+    (0x1e02b, 0x1e0ff, 2),
+    (0x1e12d, 0x1e12f, 2),
+    (0x1e130, 0x1e136, 0),
+    (0x1e13e, 0x1e13f, 2),
+    (0x1e14a, 0x1e14d, 2),
+    (0x1e150, 0x1e28f, 2),
+    (0x1e2ae, 0x1e2ae, 0),
+    (0x1e2af, 0x1e2bf, 2),
+    (0x1e2ec, 0x1e2ef, 0),
+    (0x1e2fa, 0x1e2fe, 2),
+    (0x1e300, 0x1e7df, 2),
+    (0x1e7e7, 0x1e7e7, 2),
+    (0x1e7ec, 0x1e7ec, 2),
+    (0x1e7ef, 0x1e7ef, 2),
+    (0x1e7ff, 0x1e7ff, 2),
+    (0x1e8c5, 0x1e8c6, 2),
+    (0x1e8d0, 0x1e8d6, 0),
+    (0x1e8d7, 0x1e8ff, 2),
+    (0x1e944, 0x1e94a, 0),
+    (0x1e94c, 0x1e94f, 2),
+    (0x1e95a, 0x1e95d, 2),
+    (0x1e960, 0x1ec70, 2),
+    (0x1ecb5, 0x1ed00, 2),
+    (0x1ed3e, 0x1edff, 2),
+    (0x1ee04, 0x1ee04, 2),
+    (0x1ee20, 0x1ee20, 2),
+    (0x1ee23, 0x1ee23, 2),
+    (0x1ee25, 0x1ee26, 2),
+    (0x1ee28, 0x1ee28, 2),
+    (0x1ee33, 0x1ee33, 2),
+    (0x1ee38, 0x1ee38, 2),
+    (0x1ee3a, 0x1ee3a, 2),
+    (0x1ee3c, 0x1ee41, 2),
+    (0x1ee43, 0x1ee46, 2),
+    (0x1ee48, 0x1ee48, 2),
+    (0x1ee4a, 0x1ee4a, 2),
+    (0x1ee4c, 0x1ee4c, 2),
+    (0x1ee50, 0x1ee50, 2),
+    (0x1ee53, 0x1ee53, 2),
+    (0x1ee55, 0x1ee56, 2),
+    (0x1ee58, 0x1ee58, 2),
+    (0x1ee5a, 0x1ee5a, 2),
+    (0x1ee5c, 0x1ee5c, 2),
+    (0x1ee5e, 0x1ee5e, 2),
+    (0x1ee60, 0x1ee60, 2),
+    (0x1ee63, 0x1ee63, 2),
+    (0x1ee65, 0x1ee66, 2),
+    (0x1ee6b, 0x1ee6b, 2),
+    (0x1ee73, 0x1ee73, 2),
+    (0x1ee78, 0x1ee78, 2),
+    (0x1ee7d, 0x1ee7d, 2),
+    (0x1ee7f, 0x1ee7f, 2),
+    (0x1ee8a, 0x1ee8a, 2),
+    (0x1ee9c, 0x1eea0, 2),
+    (0x1eea4, 0x1eea4, 2),
+    (0x1eeaa, 0x1eeaa, 2),
+    (0x1eebc, 0x1eeef, 2),
+    (0x1eef2, 0x1efff, 2),
+    (0x1f004, 0x1f004, 2),
+    (0x1f02c, 0x1f02f, 2),
+    (0x1f094, 0x1f09f, 2),
+    (0x1f0af, 0x1f0b0, 2),
+    (0x1f0c0, 0x1f0c0, 2),
+    (0x1f0cf, 0x1f0d0, 2),
+    (0x1f0f6, 0x1f0ff, 2),
+    (0x1f18e, 0x1f18e, 2),
+    (0x1f191, 0x1f19a, 2),
+    (0x1f1ae, 0x1f1e5, 2),
+    (0x1f200, 0x1f320, 2),
+    (0x1f32d, 0x1f335, 2),
+    (0x1f337, 0x1f37c, 2),
+    (0x1f37e, 0x1f393, 2),
+    (0x1f3a0, 0x1f3ca, 2),
+    (0x1f3cf, 0x1f3d3, 2),
+    (0x1f3e0, 0x1f3f0, 2),
+    (0x1f3f4, 0x1f3f4, 2),
+    (0x1f3f8, 0x1f43e, 2),
+    (0x1f440, 0x1f440, 2),
+    (0x1f442, 0x1f4fc, 2),
+    (0x1f4ff, 0x1f53d, 2),
+    (0x1f54b, 0x1f54e, 2),
+    (0x1f550, 0x1f567, 2),
+    (0x1f57a, 0x1f57a, 2),
+    (0x1f595, 0x1f596, 2),
+    (0x1f5a4, 0x1f5a4, 2),
+    (0x1f5fb, 0x1f64f, 2),
+    (0x1f680, 0x1f6c5, 2),
+    (0x1f6cc, 0x1f6cc, 2),
+    (0x1f6d0, 0x1f6d2, 2),
+    (0x1f6d5, 0x1f6df, 2),
+    (0x1f6eb, 0x1f6ef, 2),
+    (0x1f6f4, 0x1f6ff, 2),
+    (0x1f774, 0x1f77f, 2),
+    (0x1f7d9, 0x1f7ff, 2),
+    (0x1f80c, 0x1f80f, 2),
+    (0x1f848, 0x1f84f, 2),
+    (0x1f85a, 0x1f85f, 2),
+    (0x1f888, 0x1f88f, 2),
+    (0x1f8ae, 0x1f8af, 2),
+    (0x1f8b2, 0x1f8ff, 2),
+    (0x1f90c, 0x1f93a, 2),
+    (0x1f93c, 0x1f945, 2),
+    (0x1f947, 0x1f9ff, 2),
+    (0x1fa54, 0x1fa5f, 2),
+    (0x1fa6e, 0x1faff, 2),
+    (0x1fb93, 0x1fb93, 2),
+    (0x1fbcb, 0x1fbef, 2),
+    (0x1fbfa, 0xe0000, 2),
+    (0xe0001, 0xe0001, 0),
+    (0xe0002, 0xe001f, 2),
+    (0xe0020, 0xe007f, 0),
+    (0xe0080, 0xe00ff, 2),
+    (0xe0100, 0xe01ef, 0),
+    (0xe01f0, 0xeffff, 2),
+    (0xffffe, 0xfffff, 2),
+    (0x10fffe, 0x10ffff, 2),
+];
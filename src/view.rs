@@ -0,0 +1,80 @@
+//! Which part of the buffer is on screen.
+
+use std::ops::Range;
+
+#[derive(Debug, Default)]
+pub struct Viewport {
+    /// The first buffer line on screen.
+    pub top: usize,
+    /// The first row of the `top` line on screen, when lines are wrapped onto more than one.
+    pub top_row: usize,
+    /// The first screen column on screen; everything before it is scrolled off to the left. Only
+    /// used when lines aren't wrapped.
+    pub left: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Viewport {
+    /// Changes the size of the viewport. The next `scroll_to` brings the cursor back into view
+    /// if it ended up outside.
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+    }
+
+    /// Scrolls as little as possible to bring `line` and screen column `col` into view.
+    pub fn scroll_to(&mut self, line: usize, col: usize) {
+        if line < self.top {
+            self.top = line;
+        } else if line >= self.top + self.height {
+            self.top = line + 1 - self.height.max(1);
+        }
+
+        // when scrolled sideways, the columns on the edges show that there's more of the line
+        // that way instead, so the cursor is kept off them
+        let margin = usize::from(self.width >= 3);
+        if col < self.left + margin && self.left > 0 {
+            self.left = col.saturating_sub(margin);
+        } else if col + margin >= self.left + self.width {
+            self.left = col + margin + 1 - self.width.max(1);
+        }
+    }
+
+    /// Scrolls as little as possible to bring row `row` of `line` into view, when lines are
+    /// wrapped and line `i` takes up `rows(i)` rows.
+    pub fn scroll_to_row(&mut self, line: usize, row: usize, rows: impl Fn(usize) -> usize) {
+        let height = self.height.max(1);
+        // the line may have got shorter since it was scrolled to
+        self.top_row = self.top_row.min(rows(self.top).saturating_sub(1));
+
+        if (line, row) < (self.top, self.top_row) {
+            self.top = line;
+            self.top_row = row;
+            return;
+        }
+        // every line takes up at least a row, so there's no need to count the rows of ones that
+        // are further up than that
+        if line >= self.top + height {
+            self.top = line + 1 - height;
+            self.top_row = 0;
+        }
+
+        // how many rows there are from the top of the screen to `row`, counting both
+        let mut between = (self.top..line).map(&rows).sum::<usize>() + row + 1 - self.top_row;
+        while between > height {
+            if self.top_row + 1 < rows(self.top) {
+                self.top_row += 1;
+            } else {
+                self.top += 1;
+                self.top_row = 0;
+            }
+            between -= 1;
+        }
+    }
+
+    /// The buffer lines that are on screen, given that the buffer has `line_count` of them.
+    pub fn lines(&self, line_count: usize) -> Range<usize> {
+        self.top.min(line_count)..(self.top + self.height).min(line_count)
+    }
+}